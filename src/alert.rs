@@ -0,0 +1,204 @@
+use enum_map::{Enum, EnumMap};
+use heapless::{String, Vec};
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::clock::Clock;
+use crate::drawable::{Align, NumOfLine};
+use crate::note::{columns, note};
+
+pub const MAX_ALERTS: usize = 4; // per level
+pub const MAX_ALERT_TEXT: usize = 16;
+const ROTATE_INTERVAL: u32 = 2000; // in ms
+
+#[derive(Copy, Clone, Debug, PartialEq, Enum)]
+pub enum Level {
+    Warning,
+    Caution,
+    Advisory,
+}
+
+impl Level {
+    fn attribute(self) -> Attribute {
+        Attribute::color(match self {
+            Self::Warning => Color::Red,
+            Self::Caution => Color::Amber,
+            Self::Advisory => Color::Green,
+        })
+    }
+}
+
+struct Alert {
+    text: String<MAX_ALERT_TEXT>,
+    acknowledged: bool,
+}
+
+/// Text as stored, cut to maximum alert text
+fn truncate(text: &str) -> String<MAX_ALERT_TEXT> {
+    let mut string: String<MAX_ALERT_TEXT> = String::new();
+    for ch in text.chars() {
+        if string.push(ch).is_err() {
+            break;
+        }
+    }
+    string
+}
+
+#[derive(Default)]
+pub struct Alerts {
+    queues: EnumMap<Level, Vec<Alert, MAX_ALERTS>>,
+}
+
+impl Alerts {
+    /// Returns false if queue of specified level is full,
+    /// raising an already active alert does nothing
+    pub fn raise(&mut self, level: Level, text: &str) -> bool {
+        let queue = &mut self.queues[level];
+        let string = truncate(text);
+        if queue.iter().any(|alert| alert.text == string) {
+            return true;
+        }
+        queue
+            .push(Alert {
+                text: string,
+                acknowledged: false,
+            })
+            .is_ok()
+    }
+
+    pub fn clear(&mut self, level: Level, text: &str) {
+        let string = truncate(text);
+        self.queues[level].retain(|alert| alert.text != string);
+    }
+
+    /// Raise when active otherwise clear
+    pub fn set(&mut self, level: Level, text: &str, active: bool) -> bool {
+        if active {
+            return self.raise(level, text);
+        }
+        self.clear(level, text);
+        true
+    }
+
+    pub fn acknowledge(&mut self) {
+        for (_, queue) in self.queues.iter_mut() {
+            queue.iter_mut().for_each(|alert| alert.acknowledged = true);
+        }
+    }
+
+    pub fn is_active(&self, level: Level) -> bool {
+        !self.queues[level].is_empty()
+    }
+
+    /// Draws one line per active level, rotates when several alerts of same level are active,
+    /// unacknowledged warning and caution blinks
+    pub fn draw<T: AsMut<[u8]>>(
+        &self,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let mut index = 0;
+        for (level, queue) in self.queues.iter() {
+            if queue.is_empty() || index >= output.len() {
+                continue;
+            }
+            let alert = &queue[(clock.now / ROTATE_INTERVAL) as usize % queue.len()];
+            let blink = level != Level::Advisory && !alert.acknowledged;
+            if !blink || clock.is_on() {
+                note(alert.text.as_str(), Align::Center, &mut output[index..]);
+            }
+            let width = output[index].as_mut().len();
+            let columns = columns(alert.text.len(), Align::Center, width);
+            let mut attribute = level.attribute();
+            if blink {
+                attribute = attribute | Attribute::BLINK;
+            }
+            mark(attributes, index, columns, attribute);
+            index += 1;
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clock::Clock;
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
+
+    use super::{Alerts, Level, MAX_ALERTS, MAX_ALERT_TEXT, ROTATE_INTERVAL};
+
+    #[test]
+    fn test_alerts() {
+        let mut buffer = [[0u8; 12]; 3];
+        let mut alerts = Alerts::default();
+        assert!(!alerts.is_active(Level::Warning));
+        assert!(alerts.raise(Level::Caution, "LOW BAT"));
+        assert!(alerts.raise(Level::Warning, "ENGINE FIRE"));
+        assert!(alerts.raise(Level::Advisory, "GPS"));
+        assert_eq!(3, alerts.draw(&Clock::default(), &mut buffer, &mut []));
+        assert_eq!(
+            " ENGINE FIRE   LOW BAT       GPS    ",
+            to_utf8_string(&buffer)
+        );
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        assert_eq!(3, alerts.draw(&blink_off(), &mut buffer, &mut []));
+        assert_eq!(
+            "                             GPS    ",
+            to_utf8_string(&buffer)
+        );
+
+        alerts.acknowledge();
+        buffer.iter_mut().for_each(|b| b.zero());
+        alerts.draw(&Clock::default(), &mut buffer, &mut []);
+        assert_eq!(
+            " ENGINE FIRE   LOW BAT       GPS    ",
+            to_utf8_string(&buffer)
+        );
+
+        alerts.clear(Level::Warning, "ENGINE FIRE");
+        alerts.clear(Level::Advisory, "GPS");
+        buffer.iter_mut().for_each(|b| b.zero());
+        assert_eq!(1, alerts.draw(&Clock::default(), &mut buffer, &mut []));
+        assert!(!alerts.is_active(Level::Warning));
+        alerts.clear(Level::Caution, "LOW BAT");
+        assert_eq!(0, alerts.draw(&Clock::default(), &mut buffer, &mut []));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut buffer = [[0u8; 12]; 1];
+        let mut alerts = Alerts::default();
+        for text in ["1", "2", "3", "4"].iter() {
+            assert!(alerts.raise(Level::Advisory, text));
+        }
+        assert_eq!(MAX_ALERTS, 4);
+        assert!(!alerts.raise(Level::Advisory, "5"));
+        assert!(alerts.raise(Level::Advisory, "4"));
+        alerts.draw(&Clock::default(), &mut buffer, &mut []);
+        assert_eq!("      1     ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        let clock = Clock::new(ROTATE_INTERVAL, Default::default());
+        alerts.draw(&clock, &mut buffer, &mut []);
+        assert_eq!("      2     ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_long_text() {
+        let mut buffer = [[0u8; 20]; 1];
+        let mut alerts = Alerts::default();
+        let text = "ENGINE 1 OVER TEMPERATURE";
+        assert!(text.len() > MAX_ALERT_TEXT);
+        for _ in 0..=MAX_ALERTS {
+            assert!(alerts.raise(Level::Caution, text));
+        }
+        assert!(alerts.raise(Level::Caution, "LOW BAT"));
+        alerts.draw(&Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  ENGINE 1 OVER TE  ", to_utf8_string(&buffer));
+
+        alerts.clear(Level::Caution, "LOW BAT");
+        alerts.clear(Level::Caution, text);
+        assert!(!alerts.is_active(Level::Caution));
+    }
+}
@@ -1,34 +1,72 @@
-use heapless::String;
-
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::telemetry::Telemetry;
+use crate::format::Format;
+use crate::history::{arrows, Arrows, Trend};
+use crate::symbol::SymbolTable;
+use crate::telemetry::{Quantity, Telemetry};
+use crate::unit::AltitudeUnit;
+
+pub struct Altitude {
+    align: Align, // only accept TopRight or Right
+    unit: AltitudeUnit,
+    arrows: Arrows,
+    trend: Trend,
+}
+
+impl Altitude {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            align: Align::Right,
+            unit: AltitudeUnit::default(),
+            arrows: arrows(symbols),
+            trend: Trend::default(),
+        }
+    }
 
-pub struct Altitude(Align); // only accept TopRight or Right
+    pub fn set_unit(&mut self, unit: AltitudeUnit) {
+        self.unit = unit;
+    }
 
-impl Default for Altitude {
-    fn default() -> Self {
-        Self(Align::Right)
+    /// Arrow drawn left to the number unless steady
+    pub fn set_trend(&mut self, trend: Trend) {
+        self.trend = trend;
     }
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for Altitude {
     fn align(&self) -> Align {
-        self.0
+        self.align
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Altitude]
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        let string: String<6> = telemetry.altitude.into();
-        let bytes = string.as_bytes();
-        let offset = buffer.len() - bytes.len();
-        buffer[offset..].copy_from_slice(bytes);
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        let width = canvas.width();
+        let altitude = self.unit.convert(telemetry.altitude);
+        let label = canvas.text(0, width, self.unit.label(), Align::Right);
+        let column = label.start;
+        let range = canvas.number(0, column, altitude, &Format::default(), Align::Right);
+        if let Some(arrow) = self.trend.arrow(&self.arrows) {
+            if range.start > 0 {
+                canvas.symbol(0, range.start - 1, arrow);
+            }
+        }
         1
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
+    use crate::history::Trend;
+    use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
     use crate::test_utils::to_utf8_string;
 
@@ -36,14 +74,18 @@ mod test {
 
     #[test]
     fn test_altitude() {
-        let mut buffer = [[0u8; 6]];
-        let altitude = Altitude::default();
+        let mut buffer = [[0u8; 8]];
+        let mut altitude = Altitude::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
-        telemetry.altitude = 3000;
-        altitude.draw(&telemetry, &mut buffer);
-        assert_eq!("  3000", to_utf8_string(&buffer));
-        telemetry.altitude = 30000;
-        altitude.draw(&telemetry, &mut buffer);
-        assert_eq!(" 30000", to_utf8_string(&buffer));
+        telemetry.altitude = fixed!(914.4);
+        altitude.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  3000FT", to_utf8_string(&buffer));
+        telemetry.altitude = fixed!(9144.0);
+        altitude.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!(" 30000FT", to_utf8_string(&buffer));
+
+        altitude.set_trend(Trend::Rising);
+        altitude.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("↑30000FT", to_utf8_string(&buffer));
     }
 }
@@ -1,24 +1,71 @@
-use core::fmt::Write;
-
-use fixed_point::fixed;
-use heapless::String;
+use fixed_point::{fixed, FixedPoint};
 
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::symbol::{to_number_with_dot, Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::format::Format;
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::{Quantity, Telemetry};
+
+type Degree = FixedPoint<i8, 1>;
+
+#[derive(Copy, Clone, Debug)]
+pub struct AOAThresholds {
+    pub approach_min: Degree, // lower bound of optimum approach band
+    pub approach_max: Degree, // upper bound of optimum approach band
+    pub stall_warning: Degree,
+    pub critical: Degree,
+}
+
+impl Default for AOAThresholds {
+    fn default() -> Self {
+        Self {
+            approach_min: fixed!(6.0),
+            approach_max: fixed!(8.0),
+            stall_warning: fixed!(10.0),
+            critical: fixed!(12.0),
+        }
+    }
+}
+
+impl AOAThresholds {
+    pub fn is_approach(&self, aoa: Degree) -> bool {
+        self.approach_min <= aoa && aoa <= self.approach_max
+    }
+
+    pub fn is_stall(&self, aoa: Degree) -> bool {
+        aoa >= self.critical
+    }
+}
 
 pub struct AOA {
     alpha: SymbolIndex,
-    zero_dot: SymbolIndex,
+    format: Format,
+    thresholds: AOAThresholds,
 }
 
 impl AOA {
     pub fn new(symbols: &SymbolTable) -> Self {
         Self {
             alpha: symbols[Symbol::Alpha],
-            zero_dot: symbols[Symbol::ZeroWithTraillingDot],
+            format: Format {
+                width: 4,
+                decimals: 1,
+                zero_dot: Some(symbols[Symbol::ZeroWithTraillingDot]),
+                ..Default::default()
+            },
+            thresholds: AOAThresholds::default(),
         }
     }
+
+    pub fn set_thresholds(&mut self, thresholds: AOAThresholds) {
+        self.thresholds = thresholds;
+    }
+
+    pub fn is_stall(&self, telemetry: &Telemetry) -> bool {
+        self.thresholds.is_stall(telemetry.aoa)
+    }
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for AOA {
@@ -26,30 +73,99 @@ impl<T: AsMut<[u8]>> Drawable<T> for AOA {
         Align::Left
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        buffer[0] = self.alpha;
-        let mut string: String<4> = String::new();
-        write!(string, "{:4}", telemetry.aoa.0).ok();
-        let bytes = string.as_bytes();
-        buffer[1..5].copy_from_slice(bytes);
-        if fixed!(-1.0i8) < telemetry.aoa && telemetry.aoa < fixed!(0.0i8) {
-            buffer[2] = b'-';
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::AOA]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        canvas.symbol(0, 0, self.alpha);
+        let aoa = telemetry.aoa;
+        if aoa >= self.thresholds.stall_warning {
+            let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+            mark(attributes, 0, 1..6, attribute);
+            if !clock.is_on() {
+                return 1;
+            }
+        }
+        canvas.number(0, 1, aoa, &self.format, Align::Left);
+        if self.thresholds.is_approach(aoa) {
+            canvas.symbol(0, 1, b'[');
+            canvas.symbol(0, 5, b']');
         }
-        buffer[3] = to_number_with_dot(buffer[3], self.zero_dot);
         1
     }
 }
 
+/// Landing AOA indexer, slow, on-speed and fast chevrons from top to bottom
+pub struct AOAIndexer {
+    slow: SymbolIndex,
+    on_speed: SymbolIndex,
+    fast: SymbolIndex,
+    thresholds: AOAThresholds,
+}
+
+impl AOAIndexer {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            slow: symbols[Symbol::ChevronDown],
+            on_speed: symbols[Symbol::Circle],
+            fast: symbols[Symbol::ChevronUp],
+            thresholds: AOAThresholds::default(),
+        }
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: AOAThresholds) {
+        self.thresholds = thresholds;
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for AOAIndexer {
+    fn align(&self) -> Align {
+        Align::Center
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::AOA]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        let center = canvas.height() / 2;
+        let aoa = telemetry.aoa;
+        let (offset, symbol) = if aoa > self.thresholds.approach_max {
+            (-1, self.slow)
+        } else if aoa < self.thresholds.approach_min {
+            (1, self.fast)
+        } else {
+            (0, self.on_speed)
+        };
+        let row = match center.checked_add_signed(offset) {
+            Some(row) => row,
+            None => return 0, // too few rows
+        };
+        let width = canvas.width();
+        canvas.symbol(row, width / 2 - width / 4, symbol);
+        0
+    }
+}
+
 #[cfg(test)]
 mod test {
     use fixed_point::fixed;
 
-    use super::AOA;
+    use super::{AOAIndexer, AOA};
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
-    use crate::test_utils::to_utf8_string;
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
 
     #[test]
     fn test_aoa() {
@@ -57,12 +173,63 @@ mod test {
         let aoa = AOA::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
         telemetry.aoa = fixed!(3.1);
-        aoa.draw(&telemetry, &mut buffer);
+        aoa.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("⍺  ⒊1 ", to_utf8_string(&buffer));
 
         buffer[0].iter_mut().for_each(|x| *x = 0);
         telemetry.aoa = fixed!(-0.1);
-        aoa.draw(&telemetry, &mut buffer);
+        aoa.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("⍺ -₀1 ", to_utf8_string(&buffer));
     }
+
+    #[test]
+    fn test_aoa_thresholds() {
+        let mut buffer = [[0u8; 6]];
+        let aoa = AOA::new(&default_symbol_table());
+        let mut telemetry = Telemetry {
+            aoa: fixed!(7.0),
+            ..Default::default()
+        };
+        aoa.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("⍺[ ⒎0]", to_utf8_string(&buffer));
+        assert!(!aoa.is_stall(&telemetry));
+
+        buffer[0].zero();
+        telemetry.aoa = fixed!(10.5);
+        aoa.draw(&telemetry, &blink_off(), &mut buffer, &mut []);
+        assert_eq!("⍺     ", to_utf8_string(&buffer));
+        aoa.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("⍺ 1₀5 ", to_utf8_string(&buffer));
+        assert!(!aoa.is_stall(&telemetry));
+
+        telemetry.aoa = fixed!(12.0);
+        assert!(aoa.is_stall(&telemetry));
+    }
+
+    #[test]
+    fn test_aoa_indexer() {
+        let mut buffer = [[0u8; 8]; 3];
+        let indexer = AOAIndexer::new(&default_symbol_table());
+        let mut telemetry = Telemetry {
+            aoa: fixed!(9.0),
+            ..Default::default()
+        };
+        indexer.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  ∨                     ", to_utf8_string(&buffer));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        telemetry.aoa = fixed!(7.0);
+        indexer.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("          ○             ", to_utf8_string(&buffer));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        telemetry.aoa = fixed!(3.0);
+        indexer.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("                  ∧     ", to_utf8_string(&buffer));
+
+        let mut buffer = [[0u8; 8]; 1];
+        telemetry.aoa = fixed!(9.0);
+        indexer.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("        ", to_utf8_string(&buffer));
+    }
 }
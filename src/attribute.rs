@@ -0,0 +1,82 @@
+use core::ops::{BitOr, Range};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Color {
+    Default,
+    Red,
+    Amber,
+    Green,
+}
+
+/// Per-cell attribute, bit 7 blink, bit 6 invert, bit 0-3 palette color
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Attribute(pub u8);
+
+impl Attribute {
+    pub const NONE: Self = Self(0);
+    pub const BLINK: Self = Self(0x80);
+    pub const INVERT: Self = Self(0x40);
+
+    pub const fn color(color: Color) -> Self {
+        Self(color as u8)
+    }
+
+    pub fn is_blink(self) -> bool {
+        self.0 & Self::BLINK.0 > 0
+    }
+
+    pub fn is_invert(self) -> bool {
+        self.0 & Self::INVERT.0 > 0
+    }
+
+    /// Palette index
+    pub fn palette(self) -> u8 {
+        self.0 & 0xF
+    }
+}
+
+impl BitOr for Attribute {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// Mark cells of a row, does nothing when out of range e.g. attribute plane not supported
+pub fn mark<T: AsMut<[u8]>>(
+    attributes: &mut [T],
+    row: usize,
+    columns: Range<usize>,
+    attribute: Attribute,
+) {
+    let buffer = match attributes.get_mut(row) {
+        Some(buffer) => buffer.as_mut(),
+        None => return,
+    };
+    let end = columns.end.min(buffer.len());
+    let start = columns.start.min(end);
+    buffer[start..end]
+        .iter_mut()
+        .for_each(|byte| *byte |= attribute.0);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{mark, Attribute, Color};
+
+    #[test]
+    fn test_mark() {
+        let mut attributes = [[0u8; 4]; 2];
+        let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+        mark(&mut attributes, 1, 2..6, attribute);
+        mark(&mut attributes, 2, 0..4, Attribute::INVERT);
+        assert_eq!([[0, 0, 0, 0], [0, 0, 0x81, 0x81]], attributes);
+        assert!(Attribute(attributes[1][2]).is_blink());
+        assert!(!Attribute(attributes[1][2]).is_invert());
+        assert_eq!(1, Attribute(attributes[1][3]).palette());
+
+        let mut empty: [[u8; 4]; 0] = [];
+        mark(&mut empty, 0, 0..4, Attribute::BLINK);
+    }
+}
@@ -0,0 +1,248 @@
+use heapless::Vec;
+
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::Telemetry;
+
+pub const MAX_THRESHOLDS: usize = 4;
+
+const HORIZONTAL_STEPS: usize = 5; // LineLeft to LineRight
+const VERTICAL_STEPS: usize = 7; // LineBottom to LineTop
+
+pub type Source = fn(&Telemetry) -> i32;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    Horizontal, // fills from left to right
+    Vertical,   // fills from bottom to top
+}
+
+/// Bar graph bound to a scalar telemetry value, filled with sub-cell line glyphs
+#[derive(Clone)]
+pub struct BarGraph {
+    horizontal: [SymbolIndex; VERTICAL_STEPS], // top to bottom
+    vertical: [SymbolIndex; HORIZONTAL_STEPS], // left to right
+    source: Source,
+    min: i32,
+    max: i32,
+    length: usize,
+    orientation: Orientation,
+    align: Align,
+    thresholds: Vec<i32, MAX_THRESHOLDS>,
+}
+
+impl BarGraph {
+    pub fn new(symbols: &SymbolTable, source: Source, min: i32, max: i32) -> Self {
+        let mut horizontal = [0; VERTICAL_STEPS];
+        let slice = symbols.as_slice();
+        horizontal.copy_from_slice(&slice[Symbol::LineTop as usize..=Symbol::LineBottom as usize]);
+        let mut vertical = [0; HORIZONTAL_STEPS];
+        vertical.copy_from_slice(&slice[Symbol::LineLeft as usize..=Symbol::LineRight as usize]);
+        Self {
+            horizontal,
+            vertical,
+            source,
+            min,
+            max: max.max(min.saturating_add(1)),
+            length: 10,
+            orientation: Orientation::Horizontal,
+            align: Align::Bottom,
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Length in cells
+    pub fn set_length(&mut self, length: usize) {
+        self.length = length.max(1);
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    pub fn set_align(&mut self, align: Align) {
+        self.align = align;
+    }
+
+    /// Returns false if thresholds full
+    pub fn add_threshold(&mut self, value: i32) -> bool {
+        self.thresholds.push(value).is_ok()
+    }
+
+    /// Position in steps of value, None if below min
+    fn position(&self, value: i32, steps: usize) -> Option<usize> {
+        if value < self.min {
+            return None;
+        }
+        let total = (self.length * steps - 1) as i64;
+        let value = value.min(self.max) as i64 - self.min as i64;
+        let range = (self.max as i64 - self.min as i64).max(1);
+        Some(((value * total + range / 2) / range) as usize)
+    }
+
+    /// Bar cells, from left to right or bottom to top
+    fn cells(
+        &self,
+        value: i32,
+        steps: usize,
+        fill: u8,
+        tips: &[SymbolIndex],
+        marker: u8,
+    ) -> Vec<u8, 64> {
+        let mut cells: Vec<u8, 64> = Vec::new();
+        cells.resize(self.length.min(64), 0).ok();
+        for &threshold in self.thresholds.iter() {
+            if let Some(position) = self.position(threshold, steps) {
+                if let Some(cell) = cells.get_mut(position / steps) {
+                    *cell = marker;
+                }
+            }
+        }
+        if let Some(position) = self.position(value, steps) {
+            let index = position / steps;
+            cells.iter_mut().take(index).for_each(|cell| *cell = fill);
+            if let Some(cell) = cells.get_mut(index) {
+                *cell = tips[position % steps];
+            }
+        }
+        cells
+    }
+
+    /// Draw horizontally from column regardless of orientation and align
+    pub(crate) fn draw_horizontal<T: AsMut<[u8]>>(
+        &self,
+        value: i32,
+        canvas: &mut Canvas<T>,
+        row: usize,
+        column: usize,
+    ) {
+        let fill = self.horizontal[VERTICAL_STEPS / 2];
+        let cells = self.cells(value, HORIZONTAL_STEPS, fill, &self.vertical, b':');
+        for (i, &cell) in cells.iter().enumerate().filter(|(_, &cell)| cell > 0) {
+            canvas.symbol(row, column + i, cell);
+        }
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for BarGraph {
+    fn align(&self) -> Align {
+        self.align
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let value = (self.source)(telemetry);
+        let mut canvas = Canvas::new(output);
+        let (width, height) = (canvas.width(), canvas.height());
+        if height == 0 {
+            return 0;
+        }
+        if self.orientation == Orientation::Horizontal {
+            let row = match self.align {
+                Align::Bottom | Align::BottomLeft | Align::BottomRight => height - 1,
+                _ => 0,
+            };
+            let size = self.length.min(width);
+            let column = match self.align {
+                Align::Top | Align::Center | Align::Bottom => (width - size) / 2,
+                Align::TopRight | Align::Right | Align::BottomRight => width - size,
+                _ => 0,
+            };
+            self.draw_horizontal(value, &mut canvas, row, column);
+            return 1;
+        }
+        let fill = self.vertical[HORIZONTAL_STEPS / 2];
+        let mut tips = self.horizontal;
+        tips.reverse(); // bottom to top
+        let cells = self.cells(value, VERTICAL_STEPS, fill, &tips, b'-');
+        let size = cells.len().min(height);
+        let column = match self.align {
+            Align::Top | Align::Center | Align::Bottom => width / 2,
+            Align::TopRight | Align::Right | Align::BottomRight => width.saturating_sub(1),
+            _ => 0,
+        };
+        for (i, &cell) in cells[..size]
+            .iter()
+            .enumerate()
+            .filter(|(_, &cell)| cell > 0)
+        {
+            canvas.symbol(size - 1 - i, column, cell);
+        }
+        size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clock::Clock;
+    use crate::drawable::{Align, Drawable};
+    use crate::symbol::default_symbol_table;
+    use crate::telemetry::Telemetry;
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+
+    use super::{BarGraph, Orientation};
+
+    #[test]
+    fn test_horizontal() {
+        let mut buffer = [[0u8; 6]];
+        let mut bar = BarGraph::new(&default_symbol_table(), |t| t.throttle as i32, 0, 100);
+        bar.set_length(4);
+        bar.set_align(Align::TopLeft);
+        bar.add_threshold(75);
+        let mut telemetry = Telemetry::default();
+        bar.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("▏ :   ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.throttle = 50;
+        bar.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("──▏   ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.throttle = 100;
+        bar.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("───⎪  ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_extreme_bounds() {
+        let mut buffer = [[0u8; 4]];
+        let source = |t: &Telemetry| t.throttle as i32 * 40_000_000;
+        let mut bar = BarGraph::new(
+            &default_symbol_table(),
+            source,
+            -2_000_000_000,
+            2_000_000_000,
+        );
+        bar.set_length(4);
+        bar.set_align(Align::TopLeft);
+        let telemetry = Telemetry {
+            throttle: 50,
+            ..Default::default()
+        };
+        bar.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("───⎪", to_utf8_string(&buffer));
+
+        let bar = BarGraph::new(&default_symbol_table(), |_| i32::MAX, i32::MAX, i32::MAX);
+        bar.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+    }
+
+    #[test]
+    fn test_vertical() {
+        let mut buffer = [[0u8; 3]; 3];
+        let mut bar = BarGraph::new(&default_symbol_table(), |t| t.throttle as i32, 0, 100);
+        bar.set_length(3);
+        bar.set_orientation(Orientation::Vertical);
+        bar.set_align(Align::Left);
+        let telemetry = Telemetry {
+            throttle: 50,
+            ..Default::default()
+        };
+        assert_eq!(
+            3,
+            bar.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("   ─  |  ", to_utf8_string(&buffer));
+    }
+}
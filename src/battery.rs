@@ -1,19 +1,132 @@
-use heapless::String;
+use core::fmt::Write;
 
+use heapless::{String, Vec};
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::format::{Decimal, Format};
+use crate::history::{arrows, Arrows, Trend};
+use crate::note::columns;
 use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::telemetry::{Quantity, Telemetry};
+
+const MAX_CELL_VOLTAGE: u32 = 435; // HV LiPo, in 10mV
+const LINE_SIZE: usize = 10;
+
+type Line = Vec<u8, LINE_SIZE>;
+
+#[derive(Copy, Clone, Debug)]
+pub struct BatteryDisplay {
+    pub percentage: bool,
+    pub voltage: bool,
+    pub cell_voltage: bool,
+    pub current: bool,
+    pub consumed: bool,
+    pub flight_time: bool, // remaining flight time
+    pub home: bool,        // battery required to reach home
+}
+
+impl Default for BatteryDisplay {
+    fn default() -> Self {
+        Self {
+            percentage: true,
+            voltage: false,
+            cell_voltage: false,
+            current: false,
+            consumed: false,
+            flight_time: false,
+            home: false,
+        }
+    }
+}
 
 pub struct Battery {
-    battery: SymbolIndex,
+    levels: [SymbolIndex; 5], // empty to full
+    decimal: Format,
+    display: BatteryDisplay,
+    warning: u8,
+    reserve: u8,
+    cells: u8, // detected cell count
+    arrows: Arrows,
+    trend: Trend,
 }
 
 impl Battery {
     pub fn new(symbols: &SymbolTable) -> Self {
         Self {
-            battery: symbols[Symbol::Battery],
+            levels: [
+                symbols[Symbol::BatteryEmpty],
+                symbols[Symbol::BatteryQuarter],
+                symbols[Symbol::BatteryHalf],
+                symbols[Symbol::BatteryThreeQuarters],
+                symbols[Symbol::Battery],
+            ],
+            decimal: Format {
+                zero_dot: Some(symbols[Symbol::ZeroWithTraillingDot]),
+                ..Default::default()
+            },
+            display: BatteryDisplay::default(),
+            warning: 20,
+            reserve: 20,
+            cells: 0,
+            arrows: arrows(symbols),
+            trend: Trend::default(),
         }
     }
+
+    pub fn set_display(&mut self, display: BatteryDisplay) {
+        self.display = display;
+    }
+
+    /// Blink when remaining percentage below warning
+    pub fn set_warning(&mut self, percentage: u8) {
+        self.warning = percentage;
+    }
+
+    /// Reserve percentage on top of battery to home before flashing RTH
+    pub fn set_reserve(&mut self, percentage: u8) {
+        self.reserve = percentage;
+    }
+
+    /// Arrow drawn after the percentage unless steady
+    pub fn set_trend(&mut self, trend: Trend) {
+        self.trend = trend;
+    }
+
+    /// Detect cell count on first voltage sample, which supposed to be a charged battery
+    pub fn update(&mut self, telemetry: &Telemetry) {
+        let voltage = telemetry.battery.voltage.0 as u32;
+        if voltage == 0 {
+            self.cells = 0;
+        } else if self.cells == 0 {
+            self.cells = voltage.div_ceil(MAX_CELL_VOLTAGE) as u8;
+        }
+    }
+
+    fn cells(&self, telemetry: &Telemetry) -> u8 {
+        match telemetry.battery.cells {
+            0 => self.cells,
+            cells => cells,
+        }
+    }
+
+    // e.g. 1680 with 2 decimals, 1 precision and unit "V" to "1⒍8V", truncated
+    fn format(&self, value: u32, decimals: u8, precision: u8, unit: &str) -> Line {
+        let raw = (value / 10u32.pow((decimals - precision) as u32)) as i64;
+        let format = Format {
+            decimals: precision,
+            ..self.decimal
+        };
+        let number = format.format(Decimal {
+            raw,
+            decimals: precision,
+        });
+        let mut bytes = Line::from_slice(&number).unwrap_or_default();
+        bytes.extend_from_slice(unit.as_bytes()).ok();
+        bytes
+    }
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for Battery {
@@ -21,33 +134,238 @@ impl<T: AsMut<[u8]>> Drawable<T> for Battery {
         Align::TopRight
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        let string: String<3> = telemetry.battery.into();
-        let bytes = string.as_bytes();
-        let size = buffer.len();
-        buffer[size - bytes.len() - 1] = self.battery;
-        buffer[size - bytes.len()..].copy_from_slice(bytes);
-        1
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Battery]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let battery = &telemetry.battery;
+        let cells = self.cells(telemetry);
+        let warning = battery.remaining < self.warning;
+        let visible = !warning || clock.is_on();
+
+        let display = &self.display;
+        let mut index = 0;
+        let mut lines: [Option<Line>; 8] = Default::default();
+        if display.percentage {
+            let level = (battery.remaining as usize + 12) / 25;
+            let mut string: String<LINE_SIZE> = String::new();
+            write!(string, "{}", battery.remaining).ok();
+            let mut line = Line::new();
+            line.push(self.levels[level.min(4)]).ok();
+            line.extend_from_slice(string.as_bytes()).ok();
+            if let Some(arrow) = self.trend.arrow(&self.arrows) {
+                line.push(arrow).ok();
+            }
+            lines[0] = Some(line);
+        }
+        if display.voltage {
+            lines[1] = Some(self.format(battery.voltage.0 as u32, 2, 1, "V"));
+        }
+        if display.cell_voltage && cells > 0 {
+            let voltage = battery.voltage.0 as u32 / cells as u32;
+            lines[2] = Some(self.format(voltage, 2, 2, "V"));
+        }
+        if display.current {
+            lines[3] = Some(self.format(battery.current.0 as u32, 1, 1, "A"));
+        }
+        if display.consumed {
+            let mut string: String<LINE_SIZE> = String::new();
+            write!(string, "{}MAH", battery.consumed).ok();
+            lines[4] = Line::from_slice(string.as_bytes()).ok();
+        }
+        if display.flight_time {
+            let mut string: String<LINE_SIZE> = String::new();
+            match telemetry.flight_time_remaining() {
+                Some(time) => {
+                    let minutes = (time / 60).min(99);
+                    write!(string, "{:02}:{:02}", minutes, time % 60).ok();
+                }
+                None => string.push_str("--:--").unwrap_or_default(),
+            }
+            lines[5] = Line::from_slice(string.as_bytes()).ok();
+        }
+        if display.home {
+            let mut string: String<LINE_SIZE> = String::new();
+            match telemetry.battery_to_home() {
+                u32::MAX => string.push_str("H---MAH").unwrap_or_default(), // not moving
+                required => write!(string, "H{}MAH", required).unwrap_or_default(),
+            }
+            lines[6] = Line::from_slice(string.as_bytes()).ok();
+        }
+        if telemetry.is_bingo(self.reserve) {
+            let text: &[u8] = if clock.is_on() { b"RTH" } else { b"" };
+            lines[7] = Line::from_slice(text).ok();
+        }
+        let mut canvas = Canvas::new(output);
+        let width = canvas.width();
+        for (i, line) in lines.iter().enumerate() {
+            let line = match line {
+                Some(line) if index < canvas.height() => line,
+                _ => continue,
+            };
+            if visible {
+                canvas.bytes(index, width, line, Align::Right);
+            }
+            let columns = columns(line.len(), Align::Right, width);
+            if i == 7 {
+                let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+                mark(attributes, index, columns, attribute);
+            } else if warning {
+                let attribute = Attribute::BLINK | Attribute::color(Color::Amber);
+                mark(attributes, index, columns, attribute);
+            }
+            index += 1;
+        }
+        index
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
-    use crate::telemetry::Telemetry;
-    use crate::test_utils::to_utf8_string;
+    use crate::telemetry::{Battery as Power, Telemetry};
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
 
-    use super::Battery;
+    use super::{Battery, BatteryDisplay};
 
     #[test]
     fn test_altitude() {
         let mut buffer = [[0u8; 4]];
         let battery = Battery::new(&default_symbol_table());
-        let mut telemetry = Telemetry::default();
-        telemetry.battery = 100;
-        battery.draw(&telemetry, &mut buffer);
+        let telemetry = Telemetry {
+            battery: Power {
+                remaining: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("β100", to_utf8_string(&buffer));
     }
+
+    #[test]
+    fn test_battery_display() {
+        let mut buffer = [[0u8; 7]; 5];
+        let mut battery = Battery::new(&default_symbol_table());
+        battery.set_display(BatteryDisplay {
+            percentage: true,
+            voltage: true,
+            cell_voltage: true,
+            current: true,
+            consumed: true,
+            flight_time: false,
+            home: false,
+        });
+        let mut telemetry = Telemetry {
+            battery: Power {
+                remaining: 60,
+                voltage: fixed!(16.8),
+                current: fixed!(12.3),
+                consumed: 1200,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        battery.update(&telemetry);
+        assert_eq!(
+            5,
+            battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("    ▄60", to_utf8_string(&buffer[0..1]));
+        assert_eq!("   1⒍8V", to_utf8_string(&buffer[1..2]));
+        assert_eq!("   ⒋20V", to_utf8_string(&buffer[2..3]));
+        assert_eq!("   1⒉3A", to_utf8_string(&buffer[3..4]));
+        assert_eq!("1200MAH", to_utf8_string(&buffer[4..5]));
+
+        // cell count detected at beginning
+        buffer.iter_mut().for_each(|b| b.zero());
+        telemetry.battery.voltage = fixed!(12.0);
+        battery.update(&telemetry);
+        battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("   ⒊00V", to_utf8_string(&buffer[2..3]));
+    }
+
+    #[test]
+    fn test_battery_warning() {
+        let mut buffer = [[0u8; 4]];
+        let battery = Battery::new(&default_symbol_table());
+        let telemetry = Telemetry {
+            battery: Power {
+                remaining: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            1,
+            battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!(" ▯10", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        assert_eq!(
+            1,
+            battery.draw(&telemetry, &blink_off(), &mut buffer, &mut [])
+        );
+        assert_eq!("    ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_battery_flight_time() {
+        let mut buffer = [[0u8; 8]; 3];
+        let mut battery = Battery::new(&default_symbol_table());
+        battery.set_display(BatteryDisplay {
+            percentage: false,
+            flight_time: true,
+            home: true,
+            ..Default::default()
+        });
+        let mut telemetry = Telemetry {
+            battery: Power {
+                remaining: 75,
+                current: fixed!(10.0),
+                consumed: 500,
+                ..Default::default()
+            },
+            home_distance: 1_200,
+            ..Default::default()
+        };
+        telemetry.ground_speed = fixed!(20.0);
+        assert_eq!(
+            2,
+            battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("   09:00", to_utf8_string(&buffer[0..1]));
+        assert_eq!(" H166MAH", to_utf8_string(&buffer[1..2]));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        telemetry.home_distance = 12_000;
+        assert_eq!(
+            3,
+            battery.draw(&telemetry, &blink_off(), &mut buffer, &mut [])
+        );
+        assert_eq!("H1666MAH", to_utf8_string(&buffer[1..2]));
+        assert_eq!("        ", to_utf8_string(&buffer[2..3]));
+        assert_eq!(
+            3,
+            battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("     RTH", to_utf8_string(&buffer[2..3]));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        telemetry.ground_speed = fixed!(0.0);
+        battery.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!(" H---MAH", to_utf8_string(&buffer[1..2]));
+    }
 }
@@ -0,0 +1,203 @@
+use core::ops::Range;
+
+use crate::drawable::Align;
+use crate::format::{Decimal, Format};
+use crate::symbol::SymbolIndex;
+
+/// Rows of output or attribute plane clipped to a viewport, drawing outside is ignored
+pub struct Canvas<'a, T> {
+    rows: &'a mut [T],
+    columns: Range<usize>,
+}
+
+impl<'a, T: AsMut<[u8]>> Canvas<'a, T> {
+    pub fn new(rows: &'a mut [T]) -> Self {
+        Self {
+            rows,
+            columns: 0..usize::MAX,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Width of the viewport, 0 if no rows
+    pub fn width(&mut self) -> usize {
+        match self.line(0) {
+            Some(line) => line.len(),
+            None => 0,
+        }
+    }
+
+    /// Sub viewport with rows and columns relative to this one
+    pub fn viewport(&mut self, rows: Range<usize>, columns: Range<usize>) -> Canvas<'_, T> {
+        let end = rows.end.min(self.rows.len());
+        let start = rows.start.min(end);
+        let offset = self.columns.start;
+        let columns_end = offset.saturating_add(columns.end).min(self.columns.end);
+        let columns_start = offset.saturating_add(columns.start).min(columns_end);
+        Canvas {
+            rows: &mut self.rows[start..end],
+            columns: columns_start..columns_end,
+        }
+    }
+
+    /// Clipped row within viewport
+    fn line(&mut self, row: usize) -> Option<&mut [u8]> {
+        let buffer = self.rows.get_mut(row)?.as_mut();
+        let end = self.columns.end.min(buffer.len());
+        let start = self.columns.start.min(end);
+        Some(&mut buffer[start..end])
+    }
+
+    /// Copy bytes starting from column which may be negative, returns columns written
+    fn write(&mut self, row: usize, column: isize, bytes: &[u8]) -> Range<usize> {
+        let line = match self.line(row) {
+            Some(line) => line,
+            None => return 0..0,
+        };
+        let width = line.len() as isize;
+        let start = column.clamp(0, width);
+        let end = (column + bytes.len() as isize).clamp(start, width);
+        if start == end {
+            return 0..0;
+        }
+        let skip = (start - column) as usize;
+        let (start, end) = (start as usize, end as usize);
+        line[start..end].copy_from_slice(&bytes[skip..skip + end - start]);
+        start..end
+    }
+
+    /// Left aligned starts from column, right aligned ends before column,
+    /// centered around column, returns columns written
+    pub fn text(&mut self, row: usize, column: usize, text: &str, align: Align) -> Range<usize> {
+        self.bytes(row, column, text.as_bytes(), align)
+    }
+
+    /// Same as text but may contain symbol glyphs
+    pub fn bytes(&mut self, row: usize, column: usize, bytes: &[u8], align: Align) -> Range<usize> {
+        let len = bytes.len() as isize;
+        let column = column as isize;
+        let start = match align {
+            Align::Top | Align::Center | Align::Bottom => column - len / 2,
+            Align::TopRight | Align::Right | Align::BottomRight => column - len,
+            _ => column,
+        };
+        self.write(row, start, bytes)
+    }
+
+    /// Integer or fixed-point number rendered with format
+    pub fn number<V: Into<Decimal>>(
+        &mut self,
+        row: usize,
+        column: usize,
+        value: V,
+        format: &Format,
+        align: Align,
+    ) -> Range<usize> {
+        self.bytes(row, column, &format.format(value), align)
+    }
+
+    /// Byte at cell, None if outside viewport
+    pub fn get(&mut self, row: usize, column: usize) -> Option<u8> {
+        self.line(row)?.get(column).copied()
+    }
+
+    pub fn symbol(&mut self, row: usize, column: usize, symbol: SymbolIndex) {
+        if let Some(byte) = self.line(row).and_then(|line| line.get_mut(column)) {
+            *byte = symbol;
+        }
+    }
+
+    pub fn hline(&mut self, row: usize, columns: Range<usize>, byte: u8) {
+        if let Some(line) = self.line(row) {
+            let end = columns.end.min(line.len());
+            let start = columns.start.min(end);
+            line[start..end].iter_mut().for_each(|b| *b = byte);
+        }
+    }
+
+    pub fn vline(&mut self, rows: Range<usize>, column: usize, byte: u8) {
+        rows.for_each(|row| self.symbol(row, column, byte));
+    }
+
+    /// Outline of a box with corners of '+'
+    pub fn frame(&mut self, rows: Range<usize>, columns: Range<usize>) {
+        if rows.is_empty() || columns.is_empty() {
+            return;
+        }
+        let (bottom, right) = (rows.end - 1, columns.end - 1);
+        self.hline(rows.start, columns.clone(), b'-');
+        self.hline(bottom, columns.clone(), b'-');
+        self.vline(rows.clone(), columns.start, b'|');
+        self.vline(rows.clone(), right, b'|');
+        for row in [rows.start, bottom] {
+            self.symbol(row, columns.start, b'+');
+            self.symbol(row, right, b'+');
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::drawable::Align;
+    use crate::format::{Format, Sign};
+    use crate::test_utils::to_utf8_string;
+
+    use super::Canvas;
+
+    #[test]
+    fn test_text() {
+        let mut buffer = [[0u8; 8]; 2];
+        let mut canvas = Canvas::new(&mut buffer);
+        assert_eq!(8, canvas.width());
+        assert_eq!(0..3, canvas.text(0, 0, "ABC", Align::Left));
+        assert_eq!(7..8, canvas.text(0, 8, "DEF", Align::Center));
+        assert_eq!(0..2, canvas.text(1, 2, "GHIJ", Align::Right));
+        assert_eq!(0..0, canvas.text(2, 0, "KLM", Align::Left));
+        assert_eq!("ABC    DIJ      ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_number() {
+        let mut buffer = [[0u8; 8]; 1];
+        let mut canvas = Canvas::new(&mut buffer);
+        let format = Format {
+            width: 3,
+            sign: Sign::Always,
+            ..Default::default()
+        };
+        canvas.number(0, 0, 5, &format, Align::Left);
+        let format = Format {
+            width: 4,
+            ..Default::default()
+        };
+        canvas.number(0, 8, -42, &format, Align::Right);
+        assert_eq!(" +5  -42", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut buffer = [[0u8; 8]; 4];
+        let mut canvas = Canvas::new(&mut buffer);
+        let mut viewport = canvas.viewport(1..3, 2..6);
+        assert_eq!(2, viewport.height());
+        assert_eq!(4, viewport.width());
+        viewport.text(0, 0, "CLIPPED", Align::Left);
+        viewport.hline(1, 1..8, b'=');
+        viewport.symbol(2, 0, b'X');
+        let mut nested = viewport.viewport(0..1, 3..8);
+        assert_eq!(1, nested.width());
+        assert_eq!("          CLIP     ===          ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_frame() {
+        let mut buffer = [[0u8; 5]; 3];
+        let mut canvas = Canvas::new(&mut buffer);
+        canvas.frame(0..3, 1..5);
+        canvas.frame(0..0, 0..5);
+        assert_eq!(" +--+ |  | +--+", to_utf8_string(&buffer));
+    }
+}
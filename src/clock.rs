@@ -0,0 +1,78 @@
+pub const DEFAULT_BLINK_PERIOD: u32 = 500; // in ms
+
+#[derive(Copy, Clone, Debug)]
+pub struct Blink {
+    pub period: u32, // in ms
+    pub duty: u8,    // percentage of period being visible
+}
+
+impl Default for Blink {
+    fn default() -> Self {
+        Self {
+            period: DEFAULT_BLINK_PERIOD,
+            duty: 50,
+        }
+    }
+}
+
+/// Animation clock shared by all widgets within a frame,
+/// timestamps are milliseconds from an arbitrary epoch
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Clock {
+    pub now: u32,
+    pub blink: Blink,
+    pub native_blink: bool, // blinking applied by driver with attribute plane
+}
+
+impl Clock {
+    pub fn new(now: u32, blink: Blink) -> Self {
+        Self {
+            now,
+            blink,
+            native_blink: false,
+        }
+    }
+
+    /// Whether blinking elements are visible at the moment,
+    /// always visible when blinking applied natively
+    pub fn is_on(&self) -> bool {
+        self.native_blink || self.phase()
+    }
+
+    /// Blink phase regardless of native blinking, e.g. for alternating symbols
+    pub fn phase(&self) -> bool {
+        let period = self.blink.period.max(1);
+        self.now % period < period * self.blink.duty.min(100) as u32 / 100
+    }
+
+    /// Whether timestamp is within duration from now
+    pub fn within(&self, since: u32, duration: u32) -> bool {
+        self.now.wrapping_sub(since) < duration
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Blink, Clock};
+
+    #[test]
+    fn test_blink() {
+        let blink = Blink::default();
+        assert!(Clock::new(0, blink).is_on());
+        assert!(Clock::new(249, blink).is_on());
+        assert!(!Clock::new(250, blink).is_on());
+        assert!(Clock::new(500, blink).is_on());
+
+        let blink = Blink {
+            period: 1000,
+            duty: 75,
+        };
+        assert!(Clock::new(1700, blink).is_on());
+        assert!(!Clock::new(1750, blink).is_on());
+
+        let mut clock = Clock::new(1750, blink);
+        clock.native_blink = true;
+        assert!(clock.is_on());
+        assert!(!clock.phase());
+    }
+}
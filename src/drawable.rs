@@ -1,8 +1,10 @@
 use enum_map::Enum;
 
-use crate::telemetry::Telemetry;
+use crate::clock::Clock;
+use crate::telemetry::{Quantity, Telemetry};
 
-#[derive(Copy, Clone, PartialEq, Enum)]
+/// Widgets of same align are stacked in drawing order
+#[derive(Copy, Clone, Debug, PartialEq, Enum)]
 pub enum Align {
     Top,
     TopLeft,
@@ -17,7 +19,23 @@ pub enum Align {
 
 pub type NumOfLine = usize;
 
+/// Widget drawn into rows of output, built-in or registered with `HUD::add_widget`
 pub trait Drawable<T: AsMut<[u8]>> {
     fn align(&self) -> Align;
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine;
+
+    /// Output and attributes are rows of the align region, attributes may be empty,
+    /// returns number of lines consumed so that following widgets stack below
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine;
+
+    /// Telemetry quantities drawn, widget is replaced with placeholder or hidden by HUD
+    /// if any of them unavailable
+    fn quantities(&self) -> &[Quantity] {
+        &[]
+    }
 }
@@ -0,0 +1,232 @@
+use core::fmt::Write;
+
+use heapless::String;
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::note::columns;
+use crate::telemetry::{Quantity, Telemetry, MAX_MOTORS};
+use crate::unit::TemperatureUnit;
+
+#[derive(Copy, Clone, Debug)]
+pub struct EscDisplay {
+    pub temperature: bool, // max temperature of all ESCs
+    pub rpm: bool,         // per motor RPM in thousands
+    pub current: bool,     // per motor current in ampere
+}
+
+impl Default for EscDisplay {
+    fn default() -> Self {
+        Self {
+            temperature: true,
+            rpm: false,
+            current: false,
+        }
+    }
+}
+
+pub struct Esc {
+    display: EscDisplay,
+    temperature_limit: i16,
+    unit: TemperatureUnit,
+}
+
+impl Default for Esc {
+    fn default() -> Self {
+        Self {
+            display: EscDisplay::default(),
+            temperature_limit: 100,
+            unit: TemperatureUnit::default(),
+        }
+    }
+}
+
+impl Esc {
+    pub fn set_display(&mut self, display: EscDisplay) {
+        self.display = display;
+    }
+
+    pub fn set_unit(&mut self, unit: TemperatureUnit) {
+        self.unit = unit;
+    }
+
+    /// In celsius
+    pub fn set_temperature_limit(&mut self, limit: i16) {
+        self.temperature_limit = limit;
+    }
+
+    pub fn is_over_temperature(&self, telemetry: &Telemetry) -> bool {
+        let limit = self.temperature_limit;
+        telemetry
+            .max_esc_temperature()
+            .is_some_and(|temperature| temperature > limit)
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Esc {
+    fn align(&self) -> Align {
+        Align::Right
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Esc]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let escs = telemetry.escs();
+        if escs.is_empty() {
+            return 0;
+        }
+        let over = self.is_over_temperature(telemetry);
+        let mut lines: [String<{ MAX_MOTORS * 3 }>; 3] = Default::default();
+        if self.display.temperature {
+            let temperature = telemetry.max_esc_temperature().unwrap_or(0);
+            let temperature = self.unit.convert(temperature).rescale(0).raw;
+            write!(lines[0], "ESC{}{}", temperature, self.unit.label()).ok();
+        }
+        if self.display.rpm {
+            for esc in escs.iter() {
+                write!(lines[1], "{:3}", esc.rpm.saturating_add(500) / 1000).ok();
+            }
+        }
+        if self.display.current {
+            for esc in escs.iter() {
+                write!(lines[2], "{:3}", esc.current.0.saturating_add(5) / 10).ok();
+            }
+        }
+        let mut canvas = Canvas::new(output);
+        let width = canvas.width();
+        let mut index = 0;
+        for (i, line) in lines.iter().enumerate() {
+            if line.is_empty() || index >= canvas.height() {
+                continue;
+            }
+            if i == 0 && over {
+                let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+                mark(
+                    attributes,
+                    index,
+                    columns(line.len(), Align::Right, width),
+                    attribute,
+                );
+            }
+            if i > 0 || !over || clock.is_on() {
+                canvas.text(index, width, line.as_str(), Align::Right);
+            }
+            index += 1;
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::{fixed, FixedPoint};
+
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::telemetry::{Esc as EscStatus, Telemetry};
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
+
+    use super::{Esc, EscDisplay};
+
+    #[test]
+    fn test_esc() {
+        let mut buffer = [[0u8; 12]; 2];
+        let mut esc = Esc::default();
+        esc.set_display(EscDisplay {
+            temperature: true,
+            rpm: true,
+            current: false,
+        });
+        let mut telemetry = Telemetry {
+            motors: 4,
+            ..Default::default()
+        };
+        for (i, rpm) in [12300, 12800, 9600, 21000].iter().enumerate() {
+            telemetry.escs[i] = EscStatus {
+                rpm: *rpm,
+                temperature: 60 + i as i16,
+                ..Default::default()
+            };
+        }
+        assert_eq!(
+            2,
+            esc.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("      ESC63C", to_utf8_string(&buffer[0..1]));
+        assert_eq!(" 12 13 10 21", to_utf8_string(&buffer[1..2]));
+
+        esc.set_display(EscDisplay {
+            temperature: false,
+            rpm: false,
+            current: true,
+        });
+        telemetry.escs[2].current = fixed!(12.6);
+        buffer.iter_mut().for_each(|b| b.zero());
+        assert_eq!(
+            1,
+            esc.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("  0  0 13  0", to_utf8_string(&buffer[0..1]));
+
+        telemetry.motors = 0;
+        assert_eq!(
+            0,
+            esc.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let mut esc = Esc::default();
+        esc.set_display(EscDisplay {
+            temperature: true,
+            rpm: true,
+            current: true,
+        });
+        let mut telemetry = Telemetry {
+            motors: 1,
+            ..Default::default()
+        };
+        telemetry.escs[0] = EscStatus {
+            rpm: u32::MAX,
+            temperature: 20,
+            current: FixedPoint(u16::MAX), // commonly reported as invalid
+        };
+        for rows in 0..=3 {
+            let mut buffer = [[0u8; 8]; 3];
+            esc.draw(&telemetry, &Clock::default(), &mut buffer[..rows], &mut []);
+        }
+        let mut buffer = [[0u8; 8]; 3];
+        esc.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!(" 4294967", to_utf8_string(&buffer[1..2]));
+        assert_eq!("    6553", to_utf8_string(&buffer[2..3]));
+    }
+
+    #[test]
+    fn test_esc_over_temperature() {
+        let mut buffer = [[0u8; 8]; 1];
+        let mut esc = Esc::default();
+        esc.set_temperature_limit(80);
+        let mut telemetry = Telemetry {
+            motors: 2,
+            ..Default::default()
+        };
+        telemetry.escs[1].temperature = 85;
+        assert!(esc.is_over_temperature(&telemetry));
+        esc.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  ESC85C", to_utf8_string(&buffer));
+        buffer[0].zero();
+        esc.draw(&telemetry, &blink_off(), &mut buffer, &mut []);
+        assert_eq!("        ", to_utf8_string(&buffer));
+    }
+}
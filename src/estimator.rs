@@ -0,0 +1,404 @@
+use fixed_point::FixedPoint;
+#[allow(unused_imports)] // false warning
+use micromath::F32Ext;
+
+use crate::filter::{wrap, Channel, FieldFilter};
+use crate::telemetry::{Fix, Quantity, Telemetry, Wind};
+
+const MIN_INTERVAL: u32 = 100; // in ms, between samples of a series
+const MIN_COURSE_SPEED: i32 = 10; // in 0.1 m/s, course held below since position noise dominates
+const METERS_PER_E7_DEGREE: f32 = 0.011_131_95; // of latitude, or longitude at equator
+const WIND_SMOOTHING: f32 = 0.125; // EMA weight of new wind sample
+const MAX_CIRCLING_SAMPLES: u16 = 512; // circling drift restarts if no full turn within
+const WIND_TIMEOUT: u32 = 60_000; // in ms, estimated wind dropped if not updated within
+
+#[derive(Copy, Clone, Debug)]
+struct Position {
+    time: u32,
+    latitude: i32,  // in degree * 10^7
+    longitude: i32, // in degree * 10^7
+}
+
+/// Optional stage deriving quantities not reported by flight controller,
+/// vertical speed from altitude, ground speed and course from successive GNSS positions,
+/// supposed to be called on telemetry received
+pub struct Estimator {
+    smoothing: FieldFilter,
+    altitude: Option<(u32, i32)>, // time and altitude in 0.1 m
+    vario: Option<i32>,           // in 0.01 m/s
+    vario_filter: Channel,
+    position: Option<Position>,
+    speed: Option<i32>, // in 0.1 m/s
+    speed_filter: Channel,
+    course: Option<u16>,
+}
+
+impl Default for Estimator {
+    fn default() -> Self {
+        Self {
+            smoothing: FieldFilter {
+                smoothing: 2,
+                hysteresis: 0,
+            },
+            altitude: None,
+            vario: None,
+            vario_filter: Channel::default(),
+            position: None,
+            speed: None,
+            speed_filter: Channel::default(),
+            course: None,
+        }
+    }
+}
+
+/// Update timestamp of quantity if stamped, otherwise now
+fn sample_time(telemetry: &Telemetry, quantity: Quantity, now: u32) -> u32 {
+    match telemetry.is_stamped(quantity) {
+        true => telemetry.updated[quantity],
+        false => now,
+    }
+}
+
+impl Estimator {
+    /// Smoothing of derived vertical speed and ground speed
+    pub fn set_smoothing(&mut self, smoothing: FieldFilter) {
+        self.smoothing = smoothing;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self {
+            smoothing: self.smoothing,
+            ..Default::default()
+        };
+    }
+
+    fn update_altitude(&mut self, time: u32, altitude: i32) {
+        if let Some((last, previous)) = self.altitude {
+            let duration = time.wrapping_sub(last);
+            if duration < MIN_INTERVAL {
+                return;
+            }
+            let rate = (altitude as i64 - previous as i64) * 10_000 / duration as i64;
+            let rate = rate.clamp(i16::MIN.into(), i16::MAX.into()) as i32; // as vario
+            self.vario = Some(self.vario_filter.update(self.smoothing, rate));
+        }
+        self.altitude = Some((time, altitude));
+    }
+
+    fn update_position(&mut self, position: Position) {
+        let last = match self.position {
+            Some(last) => last,
+            None => {
+                self.position = Some(position);
+                return;
+            }
+        };
+        let duration = position.time.wrapping_sub(last.time);
+        if duration < MIN_INTERVAL {
+            return;
+        }
+        let latitude = position.latitude as f32 / 10_000_000.0;
+        let longitude = wrap_e7(position.longitude as i64 - last.longitude as i64);
+        let north = (position.latitude as i64 - last.latitude as i64) as f32 * METERS_PER_E7_DEGREE;
+        let east = longitude as f32 * METERS_PER_E7_DEGREE * latitude.to_radians().cos();
+        let speed = (north * north + east * east).sqrt() * 10_000.0 / duration as f32;
+        let speed = (speed as i32).clamp(0, u16::MAX.into()); // as ground speed
+        let speed = self.speed_filter.update(self.smoothing, speed);
+        self.speed = Some(speed);
+        if speed >= MIN_COURSE_SPEED {
+            let course = east.atan2(north).to_degrees().round() as i32;
+            self.course = Some(course.rem_euclid(360) as u16);
+        }
+        self.position = Some(position);
+    }
+
+    /// Copy of telemetry with unavailable vario, ground speed and course filled if derivable,
+    /// speed vector is relative to attitude so left to flight controller
+    pub fn apply<'a>(&mut self, now: u32, telemetry: &Telemetry<'a>) -> Telemetry<'a> {
+        if telemetry.is_available(Quantity::Altitude) {
+            let time = sample_time(telemetry, Quantity::Altitude, now);
+            self.update_altitude(time, telemetry.altitude.0);
+        } else {
+            self.altitude = None;
+            self.vario = None;
+            self.vario_filter.reset();
+        }
+        let gnss = &telemetry.gnss;
+        if telemetry.is_available(Quantity::Gnss) && gnss.fix != Fix::None {
+            self.update_position(Position {
+                time: sample_time(telemetry, Quantity::Gnss, now),
+                latitude: gnss.latitude,
+                longitude: gnss.longitude,
+            });
+        } else {
+            self.position = None;
+            self.speed = None;
+            self.speed_filter.reset();
+            self.course = None;
+        }
+
+        let mut output = *telemetry;
+        if let (false, Some(vario)) = (telemetry.is_available(Quantity::Vario), self.vario) {
+            output.vario = FixedPoint(vario.clamp(i16::MIN.into(), i16::MAX.into()) as i16);
+            output.set_derived(Quantity::Vario);
+            output.set_updated(Quantity::Vario, self.altitude.map_or(now, |(time, _)| time));
+        }
+        let updated = self.position.map_or(now, |position| position.time);
+        if let (false, Some(course)) = (telemetry.is_available(Quantity::Course), self.course) {
+            output.course = course;
+            output.set_derived(Quantity::Course);
+            output.set_updated(Quantity::Course, updated);
+        }
+        let ground_speed = (telemetry.is_available(Quantity::GroundSpeed), self.speed);
+        if let (false, Some(speed)) = ground_speed {
+            output.ground_speed = FixedPoint(speed.clamp(0, u16::MAX.into()) as u16);
+            output.set_derived(Quantity::GroundSpeed);
+            output.set_updated(Quantity::GroundSpeed, updated);
+        }
+        output
+    }
+}
+
+/// East and north components in m/s of speed towards direction in degree ref to north
+fn to_vector(speed: f32, direction: u16) -> (f32, f32) {
+    let radians = (direction as f32).to_radians();
+    (speed * radians.sin(), speed * radians.cos())
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Circling {
+    east: f32,  // sum of ground velocity
+    north: f32, //
+    samples: u16,
+    turned: i32, // in degree, signed by turn direction
+    course: Option<u16>,
+}
+
+/// Optional stage estimating wind from airspeed, heading and GNSS velocity,
+/// or from drift of ground velocity over a full turn if airspeed unavailable
+#[derive(Default)]
+pub struct WindEstimator {
+    wind: Option<(f32, f32)>, // east and north in m/s, where wind blows towards
+    updated: u32,             // in ms
+    circling: Circling,
+}
+
+impl WindEstimator {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn update(&mut self, now: u32, east: f32, north: f32) {
+        self.updated = now;
+        self.wind = Some(match self.wind {
+            Some((e, n)) => (
+                e + (east - e) * WIND_SMOOTHING,
+                n + (north - n) * WIND_SMOOTHING,
+            ),
+            None => (east, north),
+        });
+    }
+
+    /// Ground velocity traces a circle centered at wind velocity while circling
+    fn update_circling(&mut self, now: u32, east: f32, north: f32, course: u16) {
+        let circling = &mut self.circling;
+        if let Some(last) = circling.course {
+            circling.turned += wrap(course as i32 - last as i32);
+        }
+        if circling.turned.abs() >= 360 {
+            // full turn completed, current sample starts next one
+            let samples = circling.samples as f32;
+            let wind = (circling.east / samples, circling.north / samples);
+            *circling = Circling::default();
+            self.update(now, wind.0, wind.1);
+        } else if circling.samples >= MAX_CIRCLING_SAMPLES {
+            *circling = Circling::default();
+        }
+        let circling = &mut self.circling;
+        circling.course = Some(course);
+        circling.east += east;
+        circling.north += north;
+        circling.samples += 1;
+    }
+
+    /// Copy of telemetry with unavailable wind filled if estimated within timeout,
+    /// timestamps are milliseconds
+    pub fn apply<'a>(&mut self, now: u32, telemetry: &Telemetry<'a>) -> Telemetry<'a> {
+        let ground = [Quantity::GroundSpeed, Quantity::Course];
+        if ground
+            .iter()
+            .all(|&quantity| telemetry.is_available(quantity))
+        {
+            let speed = telemetry.ground_speed.0 as f32 / 10.0;
+            let (east, north) = to_vector(speed, telemetry.course);
+            let air = [Quantity::Airspeed, Quantity::Heading];
+            if air.iter().all(|&quantity| telemetry.is_available(quantity)) {
+                let speed = telemetry.airspeed.0 as f32 / 10.0;
+                let (air_east, air_north) = to_vector(speed, telemetry.heading);
+                self.update(now, east - air_east, north - air_north);
+            } else {
+                self.update_circling(now, east, north, telemetry.course);
+            }
+        }
+        if now.wrapping_sub(self.updated) >= WIND_TIMEOUT {
+            self.wind = None; // e.g. circling stopped
+        }
+
+        let mut output = *telemetry;
+        if let (false, Some((east, north))) = (telemetry.is_available(Quantity::Wind), self.wind) {
+            let speed = (east * east + north * north).sqrt();
+            let towards = east.atan2(north).to_degrees().round() as i32;
+            output.wind = Wind {
+                direction: (towards + 180).rem_euclid(360) as u16,
+                speed: FixedPoint((speed * 10.0).round().min(u16::MAX as f32) as u16),
+            };
+            output.set_derived(Quantity::Wind);
+            output.set_updated(Quantity::Wind, self.updated);
+        }
+        output
+    }
+}
+
+/// Longitude difference wrapped across antimeridian, in degree * 10^7
+fn wrap_e7(delta: i64) -> i64 {
+    const HALF_TURN: i64 = 1_800_000_000;
+    (delta + HALF_TURN).rem_euclid(2 * HALF_TURN) - HALF_TURN
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::{fixed, FixedPoint};
+
+    use crate::filter::FieldFilter;
+    use crate::telemetry::{Fix, Quantity, Telemetry};
+
+    use super::{Estimator, WindEstimator, WIND_TIMEOUT};
+
+    fn estimator() -> Estimator {
+        let mut estimator = Estimator::default();
+        estimator.set_smoothing(FieldFilter::default());
+        estimator
+    }
+
+    #[test]
+    fn test_vario() {
+        let mut estimator = estimator();
+        let mut telemetry = Telemetry::default();
+        telemetry.set_available(Quantity::Vario, false);
+        let output = estimator.apply(0, &telemetry);
+        assert!(!output.is_available(Quantity::Vario));
+
+        telemetry.altitude = fixed!(1.0);
+        let output = estimator.apply(50, &telemetry); // too soon
+        assert!(!output.is_available(Quantity::Vario));
+        let output = estimator.apply(500, &telemetry);
+        assert!(output.is_derived(Quantity::Vario));
+        assert_eq!(fixed!(2.0), output.vario);
+        assert!(!output.is_stale(Quantity::Vario, 500, 100));
+
+        // reported vario untouched
+        telemetry.set_available(Quantity::Vario, true);
+        telemetry.altitude = fixed!(2.0);
+        let output = estimator.apply(1000, &telemetry);
+        assert!(!output.is_derived(Quantity::Vario));
+        assert_eq!(fixed!(0.0), output.vario);
+    }
+
+    #[test]
+    fn test_ground_speed() {
+        let mut estimator = estimator();
+        let mut telemetry = Telemetry::default();
+        telemetry.set_available(Quantity::Speed, false);
+        telemetry.set_available(Quantity::Course, false);
+        telemetry.set_available(Quantity::GroundSpeed, false);
+        telemetry.gnss.fix = Fix::Fix3D;
+        estimator.apply(0, &telemetry);
+        telemetry.gnss.longitude = 1000; // 11.1m east at equator
+        let output = estimator.apply(1000, &telemetry);
+        assert!(!output.is_available(Quantity::Speed));
+        assert!(output.is_derived(Quantity::GroundSpeed));
+        assert_eq!(fixed!(11.1), output.ground_speed);
+        assert_eq!(1000, output.updated[Quantity::GroundSpeed]);
+        assert!(output.is_derived(Quantity::Course));
+        assert_eq!(90, output.course);
+
+        telemetry.gnss.latitude = -1000;
+        let output = estimator.apply(2000, &telemetry);
+        assert_eq!(180, output.course);
+
+        telemetry.gnss.fix = Fix::None;
+        let output = estimator.apply(3000, &telemetry);
+        assert!(!output.is_available(Quantity::GroundSpeed));
+        assert!(!output.is_available(Quantity::Course));
+    }
+
+    #[test]
+    fn test_large_jump() {
+        let mut estimator = estimator();
+        let mut telemetry = Telemetry {
+            altitude: FixedPoint(i32::MIN),
+            ..Default::default()
+        };
+        telemetry.set_available(Quantity::Vario, false);
+        telemetry.set_available(Quantity::GroundSpeed, false);
+        telemetry.gnss.fix = Fix::Fix3D;
+        telemetry.gnss.latitude = i32::MIN;
+        estimator.apply(0, &telemetry);
+        telemetry.altitude = FixedPoint(i32::MAX);
+        telemetry.gnss.latitude = i32::MAX;
+        let output = estimator.apply(1000, &telemetry);
+        assert_eq!(i16::MAX, output.vario.0);
+        assert_eq!(u16::MAX, output.ground_speed.0);
+    }
+
+    #[test]
+    fn test_wind() {
+        let mut estimator = WindEstimator::default();
+        let mut telemetry = Telemetry {
+            airspeed: fixed!(20.0),
+            ground_speed: fixed!(15.0),
+            ..Default::default()
+        };
+        telemetry.set_available(Quantity::Wind, false);
+        let output = estimator.apply(0, &telemetry);
+        assert!(output.is_derived(Quantity::Wind));
+        assert_eq!(0, output.wind.direction);
+        assert_eq!(fixed!(5.0), output.wind.speed);
+
+        // reported wind untouched
+        telemetry.set_available(Quantity::Wind, true);
+        assert_eq!(fixed!(0.0), estimator.apply(0, &telemetry).wind.speed);
+    }
+
+    #[test]
+    fn test_circling_wind() {
+        let mut estimator = WindEstimator::default();
+        let mut telemetry = Telemetry::default();
+        telemetry.set_available(Quantity::Wind, false);
+        telemetry.set_available(Quantity::Airspeed, false);
+        // 20 m/s airspeed circling with 5 m/s wind from west
+        let mut now = 0;
+        for heading in (0..=360).step_by(30) {
+            let radians = (heading as f32).to_radians();
+            let (east, north) = (20.0 * radians.sin() + 5.0, 20.0 * radians.cos());
+            let course = east.atan2(north).to_degrees().round() as i32;
+            telemetry.course = course.rem_euclid(360) as u16;
+            let speed = (east * east + north * north).sqrt();
+            telemetry.ground_speed = FixedPoint((speed * 10.0).round() as u16);
+            now += 1000;
+            let output = estimator.apply(now, &telemetry);
+            assert_eq!(heading == 360, output.is_available(Quantity::Wind));
+            if heading == 360 {
+                assert_eq!(now, output.updated[Quantity::Wind]);
+                assert_eq!(270, output.wind.direction);
+                assert_eq!(fixed!(5.0), output.wind.speed);
+            }
+        }
+
+        // circling stopped, straight flight
+        let output = estimator.apply(now + WIND_TIMEOUT - 1, &telemetry);
+        assert!(output.is_available(Quantity::Wind));
+        let output = estimator.apply(now + WIND_TIMEOUT, &telemetry);
+        assert!(!output.is_available(Quantity::Wind));
+    }
+}
@@ -0,0 +1,242 @@
+use fixed_point::FixedPoint;
+
+use crate::telemetry::{Quantity, Telemetry};
+
+const FRACTION_BITS: u32 = 8; // extra precision of EMA state
+
+/// Hysteresis here steadies printed digits, speed vector also holds its cell on screen
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FieldFilter {
+    pub smoothing: u8, // EMA weight of new sample is 1/2^smoothing, 0 means unfiltered
+    pub hysteresis: u16, // in raw unit of the field, output held until moved further than this
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FilterSettings {
+    pub vario: FieldFilter,        // raw unit 0.01 m/s
+    pub aoa: FieldFilter,          // raw unit 0.1 degree
+    pub g_force: FieldFilter,      // raw unit 0.1 g
+    pub speed_vector: FieldFilter, // raw unit 0.1 m/s for rho and degree for theta and phi
+}
+
+impl Default for FilterSettings {
+    fn default() -> Self {
+        let filter = FieldFilter {
+            smoothing: 2,
+            hysteresis: 1,
+        };
+        Self {
+            vario: filter,
+            aoa: filter,
+            g_force: filter,
+            speed_vector: filter,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Channel {
+    state: Option<i32>, // scaled by 2^FRACTION_BITS
+    output: i32,
+}
+
+impl Channel {
+    pub(crate) fn update(&mut self, filter: FieldFilter, value: i32) -> i32 {
+        let scaled = value << FRACTION_BITS;
+        let state = match self.state {
+            Some(state) => state + ((scaled - state) >> filter.smoothing.min(16)),
+            None => {
+                self.output = value;
+                scaled
+            }
+        };
+        self.state = Some(state);
+        let smoothed = (state + (1 << (FRACTION_BITS - 1))) >> FRACTION_BITS;
+        if (smoothed - self.output).abs() > filter.hysteresis as i32 {
+            self.output = smoothed;
+        }
+        self.output
+    }
+
+    /// Angle in degree wrapped within [-180, 180)
+    fn update_angle(&mut self, filter: FieldFilter, value: i32) -> i32 {
+        let mut value = value;
+        if let Some(state) = self.state {
+            let current = state >> FRACTION_BITS;
+            value = current + wrap(value - current);
+        }
+        let output = self.update(filter, value);
+        let wrapped = wrap(output);
+        if wrapped != output {
+            // keep state within range after crossing
+            let offset = (wrapped - output) << FRACTION_BITS;
+            self.state = self.state.map(|state| state + offset);
+            self.output = wrapped;
+        }
+        wrapped
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+pub(crate) fn wrap(degree: i32) -> i32 {
+    (degree + 180).rem_euclid(360) - 180
+}
+
+/// Optional smoothing stage between received telemetry and HUD,
+/// supposed to be called on telemetry received
+#[derive(Default)]
+pub struct Filter {
+    settings: FilterSettings,
+    vario: Channel,
+    aoa: Channel,
+    g_force: Channel,
+    rho: Channel,
+    theta: Channel,
+    phi: Channel,
+}
+
+impl Filter {
+    pub fn new(settings: FilterSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: FilterSettings) {
+        self.settings = settings;
+    }
+
+    /// Start over from next sample, e.g. on reconnected
+    pub fn reset(&mut self) {
+        let channels = [
+            &mut self.vario,
+            &mut self.aoa,
+            &mut self.g_force,
+            &mut self.rho,
+            &mut self.theta,
+            &mut self.phi,
+        ];
+        channels.into_iter().for_each(Channel::reset);
+    }
+
+    /// Filtered copy of telemetry, unavailable quantities pass through and restart filtering
+    pub fn apply<'a>(&mut self, telemetry: &Telemetry<'a>) -> Telemetry<'a> {
+        let mut output = *telemetry;
+        let settings = self.settings;
+        if telemetry.is_available(Quantity::Vario) {
+            let vario = self.vario.update(settings.vario, telemetry.vario.0.into());
+            output.vario = FixedPoint(vario.clamp(i16::MIN.into(), i16::MAX.into()) as i16);
+        } else {
+            self.vario.reset();
+        }
+        if telemetry.is_available(Quantity::AOA) {
+            let aoa = self.aoa.update(settings.aoa, telemetry.aoa.0.into());
+            output.aoa = FixedPoint(aoa.clamp(i8::MIN.into(), i8::MAX.into()) as i8);
+        } else {
+            self.aoa.reset();
+        }
+        if telemetry.is_available(Quantity::GForce) {
+            let g_force = self
+                .g_force
+                .update(settings.g_force, telemetry.g_force.0.into());
+            output.g_force = FixedPoint(g_force.clamp(i8::MIN.into(), i8::MAX.into()) as i8);
+        } else {
+            self.g_force.reset();
+        }
+        if telemetry.is_available(Quantity::Speed) {
+            let filter = settings.speed_vector;
+            let vector = &telemetry.speed_vector;
+            let rho = self.rho.update(filter, vector.rho.0.into());
+            output.speed_vector.rho = FixedPoint(rho.clamp(0, u16::MAX.into()) as u16);
+            output.speed_vector.theta = self.theta.update_angle(filter, vector.theta.into()) as i16;
+            let phi = self.phi.update(filter, vector.phi.into());
+            output.speed_vector.phi = phi.clamp(-90, 90) as i8;
+        } else {
+            self.rho.reset();
+            self.theta.reset();
+            self.phi.reset();
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::fixed;
+
+    use crate::telemetry::{Quantity, Telemetry};
+
+    use super::{FieldFilter, Filter, FilterSettings};
+
+    #[test]
+    fn test_ema() {
+        let mut filter = Filter::new(FilterSettings {
+            aoa: FieldFilter {
+                smoothing: 1,
+                hysteresis: 0,
+            },
+            ..Default::default()
+        });
+        let mut telemetry = Telemetry {
+            aoa: fixed!(2.0),
+            ..Default::default()
+        };
+        assert_eq!(fixed!(2.0), filter.apply(&telemetry).aoa);
+        telemetry.aoa = fixed!(4.0);
+        assert_eq!(fixed!(3.0), filter.apply(&telemetry).aoa);
+        assert_eq!(fixed!(3.5), filter.apply(&telemetry).aoa);
+        for _ in 0..16 {
+            filter.apply(&telemetry);
+        }
+        assert_eq!(fixed!(4.0), filter.apply(&telemetry).aoa);
+
+        telemetry.set_available(Quantity::AOA, false);
+        telemetry.aoa = fixed!(-1.0);
+        assert_eq!(fixed!(-1.0), filter.apply(&telemetry).aoa);
+        telemetry.set_available(Quantity::AOA, true);
+        telemetry.aoa = fixed!(8.0);
+        assert_eq!(fixed!(8.0), filter.apply(&telemetry).aoa);
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let unfiltered = FieldFilter {
+            smoothing: 0,
+            hysteresis: 2,
+        };
+        let mut filter = Filter::new(FilterSettings {
+            speed_vector: unfiltered,
+            ..Default::default()
+        });
+        let mut telemetry = Telemetry::default();
+        telemetry.speed_vector.theta = 10;
+        assert_eq!(10, filter.apply(&telemetry).speed_vector.theta);
+        for theta in [11, 9, 12, 8] {
+            telemetry.speed_vector.theta = theta;
+            assert_eq!(10, filter.apply(&telemetry).speed_vector.theta);
+        }
+        telemetry.speed_vector.theta = 13;
+        assert_eq!(13, filter.apply(&telemetry).speed_vector.theta);
+    }
+
+    #[test]
+    fn test_angle_wrap() {
+        let mut filter = Filter::new(FilterSettings {
+            speed_vector: FieldFilter {
+                smoothing: 1,
+                hysteresis: 0,
+            },
+            ..Default::default()
+        });
+        let mut telemetry = Telemetry::default();
+        telemetry.speed_vector.theta = 170;
+        filter.apply(&telemetry);
+        telemetry.speed_vector.theta = -170;
+        assert_eq!(-180, filter.apply(&telemetry).speed_vector.theta);
+        assert_eq!(-175, filter.apply(&telemetry).speed_vector.theta);
+    }
+}
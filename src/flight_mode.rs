@@ -0,0 +1,153 @@
+use enum_map::{enum_map, EnumMap};
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::note::note;
+use crate::telemetry::{FlightMode as Mode, Telemetry};
+
+const HIGHLIGHT_DURATION: u32 = 3000; // in ms
+
+pub struct FlightMode {
+    names: EnumMap<Mode, &'static str>,
+    last: Option<Mode>,
+    changed: Option<u32>, // timestamp of last mode change
+}
+
+impl Default for FlightMode {
+    fn default() -> Self {
+        Self {
+            names: enum_map! {
+                Mode::Acro => "ACRO",
+                Mode::Angle => "ANGLE",
+                Mode::Horizon => "HORIZON",
+                Mode::RTH => "RTH",
+                Mode::Loiter => "LOITER",
+                Mode::Auto => "AUTO",
+            },
+            last: None,
+            changed: None,
+        }
+    }
+}
+
+impl FlightMode {
+    /// Custom name of flight mode, e.g. "MAN" for ACRO
+    pub fn set_name(&mut self, mode: Mode, name: &'static str) {
+        self.names[mode] = name;
+    }
+
+    /// Record timestamp of flight mode change
+    pub fn update(&mut self, now: u32, telemetry: &Telemetry) {
+        let mode = telemetry.flight_mode;
+        if self.last.is_some() && self.last != Some(mode) {
+            self.changed = Some(now);
+        }
+        self.last = Some(mode);
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for FlightMode {
+    fn align(&self) -> Align {
+        Align::Left
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let mode = telemetry.flight_mode;
+        // highlight mode change by blinking
+        let highlight = match self.changed {
+            Some(changed) => clock.within(changed, HIGHLIGHT_DURATION),
+            None => false,
+        };
+        let mut index = 0;
+        if !highlight || clock.is_on() {
+            note(self.names[mode], Align::Left, output);
+        }
+        if highlight {
+            let attribute = Attribute::BLINK | Attribute::INVERT;
+            mark(attributes, index, 0..self.names[mode].len(), attribute);
+        }
+        index += 1;
+        if telemetry.armed {
+            return index;
+        }
+        for text in ["DISARMED", telemetry.arming_blocker].iter() {
+            if index >= output.len() || text.is_empty() {
+                break;
+            }
+            if !telemetry.arming_blocker.is_empty() {
+                let attribute = Attribute::color(Color::Amber);
+                mark(attributes, index, 0..text.len(), attribute);
+            }
+            index += note(text, Align::Left, &mut output[index..]);
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::telemetry::{FlightMode as Mode, Telemetry};
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+
+    use super::{FlightMode, HIGHLIGHT_DURATION};
+
+    #[test]
+    fn test_flight_mode() {
+        let mut buffer = [[0u8; 8]; 3];
+        let mut flight_mode = FlightMode::default();
+        flight_mode.set_name(Mode::Acro, "MAN");
+        let mut telemetry = Telemetry {
+            armed: true,
+            ..Default::default()
+        };
+        let clock = Clock::default();
+        assert_eq!(
+            1,
+            flight_mode.draw(&telemetry, &clock, &mut buffer, &mut [])
+        );
+        assert_eq!("MAN     ", to_utf8_string(&buffer[0..1]));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        telemetry.armed = false;
+        telemetry.arming_blocker = "THROTTLE";
+        assert_eq!(
+            3,
+            flight_mode.draw(&telemetry, &clock, &mut buffer, &mut [])
+        );
+        assert_eq!("MAN     DISARMEDTHROTTLE", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_mode_change() {
+        let mut buffer = [[0u8; 8]; 1];
+        let mut flight_mode = FlightMode::default();
+        let mut telemetry = Telemetry {
+            armed: true,
+            ..Default::default()
+        };
+        flight_mode.update(0, &telemetry);
+        telemetry.flight_mode = Mode::RTH;
+        flight_mode.update(1000, &telemetry);
+
+        let mut clock = Clock::new(1250, Default::default());
+        flight_mode.draw(&telemetry, &clock, &mut buffer, &mut []);
+        assert_eq!("        ", to_utf8_string(&buffer));
+        clock.now = 1500;
+        flight_mode.draw(&telemetry, &clock, &mut buffer, &mut []);
+        assert_eq!("RTH     ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        clock.now = 1250 + HIGHLIGHT_DURATION;
+        flight_mode.draw(&telemetry, &clock, &mut buffer, &mut []);
+        assert_eq!("RTH     ", to_utf8_string(&buffer));
+    }
+}
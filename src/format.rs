@@ -0,0 +1,259 @@
+use fixed_point::FixedPoint;
+use heapless::Vec;
+
+use crate::symbol::{to_number_with_dot, SymbolIndex};
+
+pub const MAX_NUMBER_WIDTH: usize = 12;
+pub const MAX_DECIMALS: u8 = 18; // 10^18 fits in i64
+
+pub type Number = Vec<u8, MAX_NUMBER_WIDTH>;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Sign {
+    #[default]
+    Negative, // e.g. -5, 5
+    Always, // e.g. -5, +5, 0
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Padding {
+    #[default]
+    Space, // e.g.   -5
+    Zero, // e.g. -005
+}
+
+/// Raw integer with number of decimal digits, e.g. 15 with 1 decimal means 1.5
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Decimal {
+    pub raw: i64,
+    pub decimals: u8,
+}
+
+impl Decimal {
+    /// Same value with specified decimals, rounded half away from zero
+    pub fn rescale(self, decimals: u8) -> Self {
+        Self {
+            raw: rescale(self.raw, self.decimals, decimals),
+            decimals,
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($types:ty),+) => {
+        $(
+            impl From<$types> for Decimal {
+                fn from(value: $types) -> Self {
+                    Self { raw: value as i64, decimals: 0 }
+                }
+            }
+
+            impl<const D: u8> From<FixedPoint<$types, D>> for Decimal {
+                fn from(value: FixedPoint<$types, D>) -> Self {
+                    Self { raw: value.0 as i64, decimals: D }
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(i8, u8, i16, u16, i32, u32);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Format {
+    pub width: usize,                  // in cells, 0 means as many as required
+    pub decimals: u8,                  // rounded half away from zero, at most MAX_DECIMALS
+    pub sign: Sign,                    //
+    pub padding: Padding,              //
+    pub leading_zero: bool,            // e.g. 0.5 instead of .5
+    pub zero_dot: Option<SymbolIndex>, // merge dot into preceding digit to save a cell
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            decimals: 0,
+            sign: Sign::default(),
+            padding: Padding::default(),
+            leading_zero: true,
+            zero_dot: None,
+        }
+    }
+}
+
+impl Format {
+    /// Filled with '*' if value doesn't fit in width
+    pub fn format<V: Into<Decimal>>(&self, value: V) -> Number {
+        let value = value.into();
+        let decimals = self.decimals.min(MAX_DECIMALS);
+        let raw = rescale(value.raw, value.decimals, decimals);
+        let exp = 10u64.pow(decimals as u32);
+        let (integer, fraction) = (raw.unsigned_abs() / exp, raw.unsigned_abs() % exp);
+
+        let mut body: Number = Vec::new();
+        if integer > 0 || self.leading_zero || decimals == 0 || self.zero_dot.is_some() {
+            push_digits(&mut body, integer, 1);
+        }
+        if decimals > 0 {
+            match (self.zero_dot, body.last_mut()) {
+                (Some(zero_dot), Some(last)) => *last = to_number_with_dot(*last, zero_dot),
+                _ => body.push(b'.').unwrap_or_default(),
+            }
+            push_digits(&mut body, fraction, decimals as usize);
+        }
+
+        let sign = match (raw.signum(), self.sign) {
+            (-1, _) => Some(b'-'),
+            (1, Sign::Always) => Some(b'+'),
+            _ => None,
+        };
+        let len = body.len() + sign.is_some() as usize;
+        let mut number: Number = Vec::new();
+        if self.width > 0 && (len > self.width || self.width > MAX_NUMBER_WIDTH) {
+            let width = self.width.min(MAX_NUMBER_WIDTH);
+            number.resize(width, b'*').ok();
+            return number;
+        }
+        let padding = self.width.saturating_sub(len);
+        if self.padding == Padding::Space {
+            number.resize(padding, b' ').ok();
+        }
+        if let Some(sign) = sign {
+            number.push(sign).ok();
+        }
+        if self.padding == Padding::Zero {
+            number.resize(number.len() + padding, b'0').ok();
+        }
+        number.extend_from_slice(&body).ok();
+        number
+    }
+}
+
+/// Saturated if scaled up beyond i64, 0 if scaled down beyond i64
+fn rescale(raw: i64, from: u8, to: u8) -> i64 {
+    if to >= from {
+        let exp = 10i64.checked_pow((to - from) as u32).unwrap_or(i64::MAX);
+        return raw.saturating_mul(exp);
+    }
+    let exp = match 10i64.checked_pow((from - to) as u32) {
+        Some(exp) => exp,
+        None => return 0,
+    };
+    let half = exp / 2 * raw.signum();
+    raw.saturating_add(half) / exp
+}
+
+/// Push at least width digits of value, left padded with zero
+fn push_digits(output: &mut Number, value: u64, width: usize) {
+    let mut digits = [0u8; 20];
+    let mut value = value;
+    let mut len = 0;
+    while value > 0 || len < width {
+        digits[len] = b'0' + (value % 10) as u8;
+        value /= 10;
+        len += 1;
+    }
+    for &digit in digits[..len].iter().rev() {
+        if output.push(digit).is_err() {
+            // fill with '*' as overflow
+            output.iter_mut().for_each(|byte| *byte = b'*');
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::{fixed, FixedPoint};
+
+    use crate::symbol::{default_symbol_table, Symbol};
+    use crate::test_utils::to_utf8_string;
+
+    use super::{Decimal, Format, Padding, Sign};
+
+    fn format<V: Into<Decimal>>(format: &Format, value: V) -> std::string::String {
+        to_utf8_string(&[format.format(value)])
+    }
+
+    #[test]
+    fn test_integer() {
+        let mut f = Format::default();
+        assert_eq!("0", format(&f, 0));
+        assert_eq!("-42", format(&f, -42i16));
+        f.width = 4;
+        assert_eq!(" -42", format(&f, -42));
+        f.padding = Padding::Zero;
+        assert_eq!("-042", format(&f, -42));
+        f.sign = Sign::Always;
+        assert_eq!("+042", format(&f, 42u8));
+        assert_eq!("0000", format(&f, 0));
+    }
+
+    #[test]
+    fn test_decimals() {
+        let mut f = Format {
+            decimals: 1,
+            ..Default::default()
+        };
+        assert_eq!("1.5", format(&f, fixed!(1.5i8)));
+        assert_eq!("1.3", format(&f, FixedPoint::<u16, 2>(125)));
+        assert_eq!("-1.3", format(&f, FixedPoint::<i16, 2>(-125)));
+        assert_eq!("12.0", format(&f, 12));
+        assert_eq!("-0.5", format(&f, fixed!(-0.5i8)));
+        assert_eq!("0.0", format(&f, FixedPoint::<i16, 2>(-4)));
+        f.leading_zero = false;
+        assert_eq!("-.5", format(&f, fixed!(-0.5i8)));
+        f.decimals = 0;
+        assert_eq!("-2", format(&f, fixed!(-1.5i8)));
+    }
+
+    #[test]
+    fn test_zero_dot() {
+        let zero_dot = default_symbol_table()[Symbol::ZeroWithTraillingDot];
+        let f = Format {
+            width: 4,
+            decimals: 1,
+            zero_dot: Some(zero_dot),
+            ..Default::default()
+        };
+        assert_eq!("  ⒈1", format(&f, fixed!(1.1i8)));
+        assert_eq!(" -₀5", format(&f, fixed!(-0.5i8)));
+        assert_eq!(" -⒈5", format(&f, fixed!(-1.5i8)));
+        assert_eq!(" 1⒉0", format(&f, fixed!(12.0i8)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut f = Format {
+            width: 3,
+            ..Default::default()
+        };
+        assert_eq!("999", format(&f, 999));
+        assert_eq!("***", format(&f, 1000));
+        assert_eq!("***", format(&f, -100));
+        f.width = 0;
+        assert_eq!("-2147483648", format(&f, i32::MIN));
+        f.decimals = 2;
+        assert_eq!("************", format(&f, u32::MAX));
+
+        f.width = 4;
+        for decimals in [19, 20, u8::MAX] {
+            f.decimals = decimals;
+            assert_eq!("****", format(&f, 1));
+        }
+        f.decimals = 0;
+        let tiny = Decimal {
+            raw: i64::MAX,
+            decimals: u8::MAX,
+        };
+        assert_eq!("   0", format(&f, tiny));
+        assert_eq!(i64::MAX, tiny.rescale(u8::MAX).raw);
+        assert_eq!(0, tiny.rescale(0).raw);
+        let huge = Decimal {
+            raw: -1,
+            decimals: 0,
+        };
+        assert_eq!(i64::MIN + 1, huge.rescale(u8::MAX).raw);
+    }
+}
@@ -1,21 +1,71 @@
-use core::fmt::Write;
-
-use heapless::String;
+use fixed_point::{fixed, FixedPoint};
 
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::symbol::{to_number_with_dot, Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::format::{Format, Number};
+use crate::symbol::{Symbol, SymbolTable};
+use crate::telemetry::{Quantity, Telemetry};
+
+type G = FixedPoint<i8, 1>;
 
 pub struct GForce {
-    zero_dot: SymbolIndex,
+    format: Format,
+    positive_limit: G,
+    negative_limit: G,
+    extremum: Option<(G, G)>, // (min, max)
+    show_extremum: bool,
 }
 
 impl GForce {
     pub fn new(symbols: &SymbolTable) -> Self {
         Self {
-            zero_dot: symbols[Symbol::ZeroWithTraillingDot],
+            format: Format {
+                width: 4,
+                decimals: 1,
+                zero_dot: Some(symbols[Symbol::ZeroWithTraillingDot]),
+                ..Default::default()
+            },
+            positive_limit: fixed!(9.0),
+            negative_limit: fixed!(-3.0),
+            extremum: None,
+            show_extremum: false,
         }
     }
+
+    pub fn set_limit(&mut self, positive: G, negative: G) {
+        self.positive_limit = positive;
+        self.negative_limit = negative;
+    }
+
+    /// Show held max and min beside the live value, widens the row to 11 cells
+    pub fn set_show_extremum(&mut self, show: bool) {
+        self.show_extremum = show;
+    }
+
+    pub fn reset(&mut self) {
+        self.extremum = None;
+    }
+
+    /// Hold min and max G
+    pub fn update(&mut self, telemetry: &Telemetry) {
+        self.extremum = Some(self.extremum(telemetry.g_force));
+    }
+
+    fn extremum(&self, g_force: G) -> (G, G) {
+        match self.extremum {
+            Some((min, max)) => (
+                FixedPoint(min.0.min(g_force.0)),
+                FixedPoint(max.0.max(g_force.0)),
+            ),
+            None => (g_force, g_force),
+        }
+    }
+
+    fn format(&self, g: G) -> Number {
+        self.format.format(g)
+    }
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for GForce {
@@ -23,13 +73,49 @@ impl<T: AsMut<[u8]>> Drawable<T> for GForce {
         Align::Left
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        let mut string: String<5> = String::new();
-        write!(string, "G{:4}", telemetry.g_force.0).ok();
-        buffer[..5].copy_from_slice(string.as_bytes());
-        buffer[3] = to_number_with_dot(buffer[3], self.zero_dot);
-        1
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::GForce]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let g_force = telemetry.g_force;
+        let (min, max) = self.extremum(g_force);
+
+        let mut canvas = Canvas::new(output);
+        canvas.symbol(0, 0, b'G');
+        canvas.bytes(0, 1, &self.format(g_force), Align::Left);
+        // e.g. "G  ⒈1 ⒋5/-⒈0"
+        let mut column = 6;
+        for (i, g) in [max, min].iter().enumerate().filter(|_| self.show_extremum) {
+            let bytes = self.format(*g);
+            let bytes = bytes.trim_ascii_start();
+            canvas.bytes(0, column, bytes, Align::Left);
+            column += bytes.len();
+            if i == 0 {
+                canvas.symbol(0, column, b'/');
+                column += 1;
+            }
+        }
+
+        if self.negative_limit < g_force && g_force < self.positive_limit {
+            return 1;
+        }
+        if clock.is_on() {
+            canvas.text(1, 0, "OVER-G", Align::Left);
+        }
+        mark(
+            attributes,
+            1,
+            0..6,
+            Attribute::BLINK | Attribute::color(Color::Red),
+        );
+        2
     }
 }
 
@@ -38,23 +124,82 @@ mod test {
     use fixed_point::fixed;
 
     use super::GForce;
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
-    use crate::test_utils::{to_utf8_string, ZeroSlice};
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
 
     #[test]
     fn test_g_force() {
-        let mut buffer = [[0u8; 6]];
-        let g_force = GForce::new(&default_symbol_table());
+        let mut buffer = [[0u8; 16]];
+        let mut g_force = GForce::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
         telemetry.g_force = fixed!(1.1);
-        g_force.draw(&telemetry, &mut buffer);
-        assert_eq!("G  ⒈1 ", to_utf8_string(&buffer));
+        g_force.update(&telemetry);
+        g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("G  ⒈1           ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        g_force.set_show_extremum(true);
+        g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("G  ⒈1 ⒈1/⒈1     ", to_utf8_string(&buffer));
 
         buffer[0].zero();
         telemetry.g_force = fixed!(0.9);
-        g_force.draw(&telemetry, &mut buffer);
-        assert_eq!("G  ₀9 ", to_utf8_string(&buffer));
+        g_force.update(&telemetry);
+        g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("G  ₀9 ⒈1/₀9     ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.g_force = fixed!(-0.5);
+        g_force.update(&telemetry);
+        g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("G -₀5 ⒈1/-₀5    ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        g_force.reset();
+        telemetry.g_force = fixed!(1.0);
+        g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("G  ⒈0 ⒈0/⒈0     ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_over_g() {
+        let mut buffer = [[0u8; 16]; 2];
+        let mut g_force = GForce::new(&default_symbol_table());
+        g_force.set_show_extremum(true);
+        g_force.set_limit(fixed!(4.0), fixed!(-1.0));
+        let mut telemetry = Telemetry {
+            g_force: fixed!(4.5),
+            ..Default::default()
+        };
+        g_force.update(&telemetry);
+        assert_eq!(
+            2,
+            g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("OVER-G          ", to_utf8_string(&buffer[1..]));
+
+        buffer[1].zero();
+        assert_eq!(
+            2,
+            g_force.draw(&telemetry, &blink_off(), &mut buffer, &mut [])
+        );
+        assert_eq!("                ", to_utf8_string(&buffer[1..]));
+
+        telemetry.g_force = fixed!(-1.0);
+        g_force.update(&telemetry);
+        assert_eq!(
+            2,
+            g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+
+        telemetry.g_force = fixed!(3.9);
+        assert_eq!(
+            1,
+            g_force.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("G  ⒊9 ⒋5/-⒈0    ", to_utf8_string(&buffer[..1]));
     }
 }
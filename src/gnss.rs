@@ -0,0 +1,317 @@
+use core::f64::consts::PI;
+use core::fmt::Write;
+
+use heapless::String;
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::{Fix, Quantity, Telemetry};
+
+const E7: i64 = 10_000_000;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CoordinateFormat {
+    Decimal, // e.g. N22.54312
+    DMS,     // e.g. N22 32 35.2
+    MGRS,    // e.g. 50QJK97389592, 10 meters precision
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct GnssDisplay {
+    pub hdop: bool,
+    pub coordinate: Option<CoordinateFormat>,
+}
+
+pub struct Gnss {
+    satellite: SymbolIndex,
+    display: GnssDisplay,
+}
+
+impl Gnss {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            satellite: symbols[Symbol::Satellite],
+            display: GnssDisplay::default(),
+        }
+    }
+
+    pub fn set_display(&mut self, display: GnssDisplay) {
+        self.display = display;
+    }
+}
+
+fn hemisphere(value: i32, positive: char, negative: char) -> char {
+    if value >= 0 {
+        positive
+    } else {
+        negative
+    }
+}
+
+fn write_decimal(string: &mut String<16>, value: i32, positive: char, negative: char) {
+    let abs = (value as i64).abs();
+    let hemisphere = hemisphere(value, positive, negative);
+    write!(string, "{}{}.{:05}", hemisphere, abs / E7, abs % E7 / 100).ok();
+}
+
+fn write_dms(string: &mut String<16>, value: i32, positive: char, negative: char) {
+    let abs = (value as i64).abs();
+    let hemisphere = hemisphere(value, positive, negative);
+    let deci_seconds = abs % E7 * 36000 / E7;
+    let (minutes, deci_seconds) = (deci_seconds / 600, deci_seconds % 600);
+    let (seconds, decimal) = (deci_seconds / 10, deci_seconds % 10);
+    write!(
+        string,
+        "{}{} {:02} {:02}.{}",
+        hemisphere,
+        abs / E7,
+        minutes,
+        seconds,
+        decimal
+    )
+    .ok();
+}
+
+// Taylor series since no_std lacks of trigonometric functions,
+// micromath approximations are not precise enough for UTM
+fn sin(x: f64) -> f64 {
+    let mut x = x % (2.0 * PI);
+    if x > PI {
+        x -= 2.0 * PI;
+    } else if x < -PI {
+        x += 2.0 * PI;
+    }
+    let (mut sum, mut term) = (x, x);
+    for n in 1..16 {
+        term *= -x * x / ((2 * n) * (2 * n + 1)) as f64;
+        sum += term;
+    }
+    sum
+}
+
+fn cos(x: f64) -> f64 {
+    sin(x + PI / 2.0)
+}
+
+/// Returns zone, easting and northing in meters
+fn to_utm(latitude: i32, longitude: i32) -> (u8, f64, f64) {
+    const K0: f64 = 0.9996;
+    const A: f64 = 6378137.0;
+    const F: f64 = 1.0 / 298.257223563;
+    const E2: f64 = F * (2.0 - F);
+    const EP2: f64 = E2 / (1.0 - E2);
+    const E4: f64 = E2 * E2;
+    const E6: f64 = E4 * E2;
+
+    let zone = ((longitude as i64 + 180 * E7) / (6 * E7) + 1).min(60) as u8;
+    let central_meridian = (zone as i64 - 1) * 6 - 180 + 3;
+    let phi = (latitude as f64 / E7 as f64).to_radians();
+    let lambda = ((longitude as i64 - central_meridian * E7) as f64 / E7 as f64).to_radians();
+
+    let (sin_phi, cos_phi) = (sin(phi), cos(phi));
+    let u = E2 * sin_phi * sin_phi;
+    let n = A * (1.0 + u / 2.0 + 3.0 * u * u / 8.0 + 5.0 * u * u * u / 16.0); // a / sqrt(1 - u)
+    let tan_phi = sin_phi / cos_phi;
+    let t = tan_phi * tan_phi;
+    let c = EP2 * cos_phi * cos_phi;
+    let a = cos_phi * lambda;
+    let m = A
+        * ((1.0 - E2 / 4.0 - 3.0 * E4 / 64.0 - 5.0 * E6 / 256.0) * phi
+            - (3.0 * E2 / 8.0 + 3.0 * E4 / 32.0 + 45.0 * E6 / 1024.0) * sin(2.0 * phi)
+            + (15.0 * E4 / 256.0 + 45.0 * E6 / 1024.0) * sin(4.0 * phi)
+            - (35.0 * E6 / 3072.0) * sin(6.0 * phi));
+
+    let (a2, a3) = (a * a, a * a * a);
+    let (a4, a5, a6) = (a3 * a, a3 * a2, a3 * a3);
+    let easting = K0
+        * n
+        * (a + (1.0 - t + c) * a3 / 6.0
+            + (5.0 - 18.0 * t + t * t + 72.0 * c - 58.0 * EP2) * a5 / 120.0)
+        + 500000.0;
+    let mut northing = K0
+        * (m + n
+            * tan_phi
+            * (a2 / 2.0
+                + (5.0 - t + 9.0 * c + 4.0 * c * c) * a4 / 24.0
+                + (61.0 - 58.0 * t + t * t + 600.0 * c - 330.0 * EP2) * a6 / 720.0));
+    if latitude < 0 {
+        northing += 10000000.0;
+    }
+    (zone, easting, northing)
+}
+
+fn write_mgrs(string: &mut String<16>, latitude: i32, longitude: i32) {
+    const BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+    const COLUMNS: [&[u8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+    const ROWS: &[u8] = b"ABCDEFGHJKLMNPQRSTUV";
+
+    let (zone, easting, northing) = to_utm(latitude, longitude);
+    let (easting, northing) = (easting as u32, northing as u32);
+    let band = BANDS[((latitude as i64 + 80 * E7) / (8 * E7)).clamp(0, 19) as usize];
+    let column = COLUMNS[(zone as usize - 1) % 3][(easting / 100000).clamp(1, 8) as usize - 1];
+    let offset = if zone % 2 == 0 { 5 } else { 0 };
+    let row = ROWS[(northing / 100000 + offset) as usize % ROWS.len()];
+    let (easting, northing) = (easting % 100000 / 10, northing % 100000 / 10);
+    let (band, column, row) = (band as char, column as char, row as char);
+    write!(
+        string,
+        "{}{}{}{}{:04}{:04}",
+        zone, band, column, row, easting, northing
+    )
+    .ok();
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Gnss {
+    fn align(&self) -> Align {
+        Align::BottomLeft
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Gnss]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let gnss = &telemetry.gnss;
+        let lost = gnss.fix == Fix::None;
+        let visible = !lost || clock.is_on();
+
+        let mut lines: [String<16>; 4] = Default::default();
+        let fix = match gnss.fix {
+            Fix::None => "NO",
+            Fix::Fix2D => "2D",
+            Fix::Fix3D => "3D",
+        };
+        write!(lines[0], " {:2} {}", gnss.satellites, fix).ok();
+        if self.display.hdop {
+            write!(lines[1], "HDOP{}", gnss.hdop).ok();
+        }
+        let (latitude, longitude) = (gnss.latitude, gnss.longitude);
+        match self.display.coordinate {
+            Some(CoordinateFormat::Decimal) => {
+                write_decimal(&mut lines[2], latitude, 'N', 'S');
+                write_decimal(&mut lines[3], longitude, 'E', 'W');
+            }
+            Some(CoordinateFormat::DMS) => {
+                write_dms(&mut lines[2], latitude, 'N', 'S');
+                write_dms(&mut lines[3], longitude, 'E', 'W');
+            }
+            Some(CoordinateFormat::MGRS) => write_mgrs(&mut lines[2], latitude, longitude),
+            None => (),
+        }
+
+        let mut canvas = Canvas::new(output);
+        let width = canvas.width();
+        let num_lines = lines.iter().filter(|line| !line.is_empty()).count();
+        let num_lines = num_lines.min(canvas.height());
+        let offset = canvas.height() - num_lines;
+        let lines = lines.iter().filter(|line| !line.is_empty());
+        for (index, line) in lines.take(num_lines).enumerate() {
+            let row = offset + index;
+            if lost {
+                let attribute = Attribute::BLINK | Attribute::color(Color::Amber);
+                mark(attributes, row, 0..line.len().min(width), attribute);
+            }
+            if !visible {
+                continue;
+            }
+            canvas.text(row, 0, line.as_str(), Align::Left);
+            if index == 0 {
+                canvas.symbol(row, 0, self.satellite);
+            }
+        }
+        num_lines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::string::String as StdString;
+
+    use fixed_point::fixed;
+    use heapless::String;
+
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::symbol::default_symbol_table;
+    use crate::telemetry::{Fix, Gnss as GnssStatus, Telemetry};
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
+
+    use super::{write_mgrs, CoordinateFormat, Gnss, GnssDisplay};
+
+    fn mgrs(latitude: i32, longitude: i32) -> StdString {
+        let mut string: String<16> = String::new();
+        write_mgrs(&mut string, latitude, longitude);
+        StdString::from(string.as_str())
+    }
+
+    #[test]
+    fn test_mgrs() {
+        assert_eq!("18SUJ23470648", mgrs(388895000, -770353000));
+        assert_eq!("50QJK97389592", mgrs(225431200, 1140578300));
+        assert_eq!("56HLH34905228", mgrs(-338568000, 1512153000));
+    }
+
+    #[test]
+    fn test_gnss() {
+        let mut buffer = [[0u8; 12]; 4];
+        let mut gnss = Gnss::new(&default_symbol_table());
+        gnss.set_display(GnssDisplay {
+            hdop: true,
+            coordinate: Some(CoordinateFormat::Decimal),
+        });
+        let mut telemetry = Telemetry {
+            gnss: GnssStatus {
+                satellites: 12,
+                fix: Fix::Fix3D,
+                hdop: fixed!(0.9),
+                latitude: 225431200,
+                longitude: -1140578300,
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            4,
+            gnss.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("⊕12 3D      ", to_utf8_string(&buffer[0..1]));
+        assert_eq!("HDOP0.9     ", to_utf8_string(&buffer[1..2]));
+        assert_eq!("N22.54312   ", to_utf8_string(&buffer[2..3]));
+        assert_eq!("W114.05783  ", to_utf8_string(&buffer[3..4]));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        gnss.set_display(GnssDisplay {
+            hdop: false,
+            coordinate: Some(CoordinateFormat::DMS),
+        });
+        assert_eq!(
+            3,
+            gnss.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("⊕12 3D      ", to_utf8_string(&buffer[1..2]));
+        assert_eq!("N22 32 35.2 ", to_utf8_string(&buffer[2..3]));
+        assert_eq!("W114 03 28.1", to_utf8_string(&buffer[3..4]));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        gnss.set_display(GnssDisplay::default());
+        telemetry.gnss.fix = Fix::None;
+        telemetry.gnss.satellites = 3;
+        assert_eq!(
+            1,
+            gnss.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("⊕ 3 NO      ", to_utf8_string(&buffer[3..4]));
+        buffer.iter_mut().for_each(|b| b.zero());
+        assert_eq!(1, gnss.draw(&telemetry, &blink_off(), &mut buffer, &mut []));
+        assert_eq!("            ", to_utf8_string(&buffer[3..4]));
+    }
+}
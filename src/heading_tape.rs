@@ -1,11 +1,12 @@
-use core::cell::Cell;
 use core::cmp::{max, min};
 
 use heapless::String;
 
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
 use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::telemetry::{Quantity, Telemetry};
 
 const HEADING_TAPE_WIDTH: usize = 3 * 5; // e.g. "350 . 000 . 010"
 const MAX_OFFSET: isize = HEADING_TAPE_WIDTH as isize / 2;
@@ -26,25 +27,26 @@ fn theta_to_offset(theta: i16) -> usize {
 
 pub struct HeadingTape {
     steerpoint_indicator: SymbolIndex,
-    counter: Cell<usize>,
 }
 
 impl HeadingTape {
     pub fn new(symbols: &SymbolTable) -> Self {
         Self {
             steerpoint_indicator: symbols[Symbol::BoxDrawningLightUp],
-            counter: Cell::new(0),
         }
     }
 
-    fn draw_indicator(&self, wp_theta: i16, output: &mut [u8]) {
-        let center = output.len() / 2;
-        let wp_offset = theta_to_offset(wp_theta) + center - HEADING_TAPE_WIDTH / 2;
-        if self.counter.get() % 2 == 0 || wp_offset != center {
-            output[center] = b'^';
+    fn draw_indicator<T: AsMut<[u8]>>(&self, wp_theta: i16, clock: &Clock, canvas: &mut Canvas<T>) {
+        let center = canvas.width() / 2;
+        let wp_offset = (theta_to_offset(wp_theta) + center).checked_sub(HEADING_TAPE_WIDTH / 2);
+        // steerpoint indicator alternates with heading marker when overlapped
+        if clock.phase() || wp_offset != Some(center) {
+            canvas.symbol(1, center, b'^');
         }
-        if self.counter.get() % 2 == 1 || wp_offset != center {
-            output[wp_offset] = self.steerpoint_indicator;
+        if let Some(offset) = wp_offset {
+            if !clock.phase() || offset != center {
+                canvas.symbol(1, offset, self.steerpoint_indicator);
+            }
         }
     }
 }
@@ -54,15 +56,26 @@ impl<T: AsMut<[u8]>> Drawable<T> for HeadingTape {
         Align::Top
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        draw_tape(telemetry.heading, output[0].as_mut());
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Heading]
+    }
 
-        let mut theta = ((telemetry.steerpoint.heading + 360 - telemetry.heading) % 360) as i16;
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        _: &mut [T],
+    ) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        draw_tape(telemetry.heading, &mut canvas);
+
+        let (steerpoint, heading) = (telemetry.steerpoint.heading, telemetry.heading);
+        let mut theta = (steerpoint % 360 + 360 - heading % 360) as i16 % 360;
         if theta > 180 {
             theta = theta - 360
         }
-        self.draw_indicator(theta, output[1].as_mut());
-        self.counter.set(self.counter.get() + 1);
+        self.draw_indicator(theta, clock, &mut canvas);
         2
     }
 }
@@ -74,7 +87,8 @@ fn draw_heading(output: &mut [u8], heading: u16) {
     output[(3 - bytes.len())..3].copy_from_slice(bytes);
 }
 
-fn draw_tape(heading: u16, output: &mut [u8]) {
+fn draw_tape<T: AsMut<[u8]>>(heading: u16, canvas: &mut Canvas<T>) {
+    let heading = heading % 360;
     let mut buffer: [u8; HEADING_TAPE_WIDTH + 4] = [b' '; HEADING_TAPE_WIDTH + 4];
     let lower_heading = heading / 10 * 10;
     let upper_heading = lower_heading + 10;
@@ -97,16 +111,17 @@ fn draw_tape(heading: u16, output: &mut [u8]) {
     buffer[lower_index - 2] = b'.';
     buffer[lower_index + 4] = b'.';
     buffer[upper_index + 4] = b'.';
-    let index = output.len() / 2 - HEADING_TAPE_WIDTH / 2;
-    output[index..index + HEADING_TAPE_WIDTH].copy_from_slice(&buffer[2..2 + HEADING_TAPE_WIDTH]);
+    let center = canvas.width() / 2;
+    canvas.bytes(0, center, &buffer[2..2 + HEADING_TAPE_WIDTH], Align::Center);
 }
 
 #[cfg(test)]
 mod test {
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
-    use crate::test_utils::{to_utf8_string, ZeroSlice};
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
 
     use super::{draw_heading, HeadingTape, HEADING_TAPE_WIDTH};
 
@@ -126,10 +141,10 @@ mod test {
         let mut buffer: [[u8; HEADING_TAPE_WIDTH + 2]; 2] = [[0; HEADING_TAPE_WIDTH + 2]; 2];
         let tape = HeadingTape::new(&default_symbol_table());
         let telemetry = Telemetry::default();
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!(" 350 . 000 . 010 ", to_utf8_string(&buffer[0..1]));
         assert_eq!("        ^        ", to_utf8_string(&buffer[1..2]));
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &blink_off(), &mut buffer, &mut []);
         assert_eq!("        ╵        ", to_utf8_string(&buffer[1..2]));
     }
 
@@ -139,13 +154,13 @@ mod test {
         let tape = HeadingTape::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
         telemetry.heading = 359;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("  350 . 000 . 01 ", to_utf8_string(&buffer[0..1]));
         telemetry.heading = 358;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!(" . 350 . 000 . 0 ", to_utf8_string(&buffer[0..1]));
         telemetry.heading = 356;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("  . 350 . 000 .  ", to_utf8_string(&buffer[0..1]));
     }
 
@@ -155,23 +170,27 @@ mod test {
         let tape = HeadingTape::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
         telemetry.steerpoint.heading = 0;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!(" 350 . 000 . 010 ", to_utf8_string(&buffer[0..1]));
         assert_eq!("        ^        ", to_utf8_string(&buffer[1..2]));
 
         buffer[1].zero();
         telemetry.steerpoint.heading = 90;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("        ^      ╵ ", to_utf8_string(&buffer[1..2]));
 
         buffer[1].zero();
         telemetry.steerpoint.heading = 180;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("        ^      ╵ ", to_utf8_string(&buffer[1..2]));
 
         buffer[1].zero();
         telemetry.steerpoint.heading = 270;
-        tape.draw(&telemetry, &mut buffer);
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!(" ╵      ^        ", to_utf8_string(&buffer[1..2]));
+
+        let mut buffer = [[0u8; 1]; 2];
+        tape.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("0^", to_utf8_string(&buffer));
     }
 }
@@ -1,13 +1,23 @@
-use heapless::String;
+use heapless::Vec;
 
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::telemetry::Telemetry;
+use crate::format::{Format, MAX_NUMBER_WIDTH};
+use crate::telemetry::{Quantity, Telemetry};
+use crate::unit::AltitudeUnit;
 
-pub struct Height(Align); // Right only
+pub struct Height(Align, AltitudeUnit); // Right only
 
 impl Default for Height {
     fn default() -> Self {
-        Self(Align::Bottom)
+        Self(Align::Bottom, AltitudeUnit::default())
+    }
+}
+
+impl Height {
+    pub fn set_unit(&mut self, unit: AltitudeUnit) {
+        self.1 = unit;
     }
 }
 
@@ -16,24 +26,31 @@ impl<T: AsMut<[u8]>> Drawable<T> for Height {
         self.0
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        if telemetry.height == i16::MIN {
-            return 0;
-        }
-        let mut buffer = output[0].as_mut();
-        if self.0 == Align::Bottom {
-            buffer = output[output.len() - 1].as_mut();
-        }
-        let string: String<6> = telemetry.height.into();
-        let bytes = string.as_bytes();
-        let offset = buffer.len() / 2 - bytes.len() / 2;
-        buffer[offset..offset + bytes.len()].copy_from_slice(bytes);
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Height]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        let row = match self.0 {
+            Align::Bottom => canvas.height().saturating_sub(1),
+            _ => 0,
+        };
+        let center = canvas.width() / 2;
+        let mut bytes: Vec<u8, { MAX_NUMBER_WIDTH + 2 }> = Vec::new();
+        let height = Format::default().format(self.1.convert(telemetry.height));
+        bytes.extend_from_slice(&height).ok();
+        bytes.extend_from_slice(self.1.label().as_bytes()).ok();
+        canvas.bytes(row, center, &bytes, Align::Center);
         1
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::telemetry::Telemetry;
     use crate::test_utils::to_utf8_string;
@@ -45,13 +62,8 @@ mod test {
         let mut buffer = [[0u8; 7]];
         let height = Height::default();
         let mut telemetry = Telemetry::default();
-        telemetry.height = 98;
-        height.draw(&telemetry, &mut buffer);
-        assert_eq!("  98   ", to_utf8_string(&buffer));
-
-        buffer[0].iter_mut().for_each(|x| *x = 0);
-        telemetry.height = i16::MIN;
-        height.draw(&telemetry, &mut buffer);
-        assert_eq!("       ", to_utf8_string(&buffer));
+        telemetry.height = fixed!(29.9);
+        height.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!(" 98FT  ", to_utf8_string(&buffer));
     }
 }
@@ -0,0 +1,185 @@
+use fixed_point::FixedPoint;
+use heapless::HistoryBuffer;
+
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::{Quantity, Speed, Telemetry};
+
+pub const HISTORY_LENGTH: usize = 16;
+pub const SAMPLE_INTERVAL: u32 = 200; // in ms, full history spans 3.2 seconds
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Trend {
+    #[default]
+    Steady,
+    Rising,
+    Falling,
+}
+
+/// Up and down arrow symbols
+pub type Arrows = [SymbolIndex; 2];
+
+pub fn arrows(symbols: &SymbolTable) -> Arrows {
+    [symbols[Symbol::ArrowUp], symbols[Symbol::ArrowDown]]
+}
+
+impl Trend {
+    pub fn arrow(self, arrows: &Arrows) -> Option<SymbolIndex> {
+        match self {
+            Self::Steady => None,
+            Self::Rising => Some(arrows[0]),
+            Self::Falling => Some(arrows[1]),
+        }
+    }
+}
+
+/// Trended quantities of a telemetry sample, None if unavailable or stale
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Sample {
+    pub time: u32,                            // in ms
+    pub altitude: Option<FixedPoint<i32, 1>>, // in meters
+    pub speed: Option<Speed>,                 //
+    pub battery: Option<u8>,                  // remaining percentage
+}
+
+/// Ring buffer of recent telemetry samples
+#[derive(Default)]
+pub struct History {
+    samples: HistoryBuffer<Sample, HISTORY_LENGTH>,
+}
+
+impl History {
+    /// Record a sample at most every sample interval, timestamps are milliseconds,
+    /// quantities not updated within timeout are left out, 0 disables the timeout
+    pub fn push(&mut self, now: u32, telemetry: &Telemetry, timeout: u32) {
+        if let Some(latest) = self.samples.recent() {
+            if now.wrapping_sub(latest.time) < SAMPLE_INTERVAL {
+                return;
+            }
+        }
+        let valid = |quantity| {
+            telemetry.is_available(quantity) && !telemetry.is_stale(quantity, now, timeout)
+        };
+        self.samples.write(Sample {
+            time: now,
+            altitude: valid(Quantity::Altitude).then_some(telemetry.altitude),
+            speed: valid(Quantity::Speed).then_some(telemetry.speed()),
+            battery: valid(Quantity::Battery).then_some(telemetry.battery.remaining),
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.samples = HistoryBuffer::new();
+    }
+
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.recent()
+    }
+
+    /// Value extrapolated linearly to seconds after the latest sample, over samples since
+    /// the value last missing, None if missing in the latest sample or spans no time
+    pub fn predict(&self, value: fn(&Sample) -> Option<i32>, seconds: u32) -> Option<i32> {
+        let mut span: Option<((u32, i32), (u32, i32))> = None;
+        for sample in self.samples.oldest_ordered() {
+            span = match (value(sample), span) {
+                (Some(new), Some((oldest, _))) => Some((oldest, (sample.time, new))),
+                (Some(new), None) => Some(((sample.time, new), (sample.time, new))),
+                (None, _) => None,
+            };
+        }
+        let ((old_time, old), (new_time, new)) = span?;
+        let duration = new_time.wrapping_sub(old_time) as i64;
+        if duration == 0 {
+            return None;
+        }
+        let (old, new) = (old as i64, new as i64);
+        let predicted = new + (new - old) * seconds as i64 * 1000 / duration;
+        Some(predicted.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+
+    /// Rising or falling if predicted change after seconds reaches threshold
+    pub fn trend(&self, value: fn(&Sample) -> Option<i32>, seconds: u32, threshold: i32) -> Trend {
+        let latest = self.latest().and_then(value);
+        let (latest, predicted) = match (latest, self.predict(value, seconds)) {
+            (Some(latest), Some(predicted)) => (latest, predicted),
+            _ => return Trend::Steady,
+        };
+        if predicted - latest >= threshold {
+            Trend::Rising
+        } else if latest - predicted >= threshold {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::fixed;
+
+    use crate::telemetry::{Quantity, Telemetry};
+
+    use super::{History, Sample, Trend, HISTORY_LENGTH, SAMPLE_INTERVAL};
+
+    #[test]
+    fn test_predict() {
+        let mut history = History::default();
+        let mut telemetry = Telemetry::default();
+        let altitude = |sample: &Sample| sample.altitude.map(|altitude| altitude.0);
+        history.push(0, &telemetry, 0);
+        assert_eq!(None, history.predict(altitude, 10));
+        assert_eq!(Trend::Steady, history.trend(altitude, 10, 1));
+
+        telemetry.altitude = fixed!(1.0);
+        history.push(100, &telemetry, 0); // within sample interval
+        assert_eq!(None, history.predict(altitude, 10));
+        history.push(1000, &telemetry, 0);
+        assert_eq!(Some(110), history.predict(altitude, 10));
+        assert_eq!(Trend::Rising, history.trend(altitude, 10, 100));
+        assert_eq!(Trend::Steady, history.trend(altitude, 10, 101));
+
+        // oldest samples dropped
+        for i in 0..HISTORY_LENGTH as u32 {
+            history.push(1000 + (i + 1) * SAMPLE_INTERVAL, &telemetry, 0);
+        }
+        assert_eq!(Some(10), history.predict(altitude, 10));
+        telemetry.altitude = fixed!(0.0);
+        history.push(5000, &telemetry, 0);
+        assert_eq!(Trend::Falling, history.trend(altitude, 10, 10));
+
+        history.clear();
+        assert!(history.latest().is_none());
+    }
+
+    #[test]
+    fn test_missing() {
+        let mut history = History::default();
+        let mut telemetry = Telemetry::default();
+        let altitude = |sample: &Sample| sample.altitude.map(|altitude| altitude.0);
+        let battery = |sample: &Sample| sample.battery.map(i32::from);
+        telemetry.battery.remaining = 100;
+        history.push(0, &telemetry, 0);
+        telemetry.altitude = fixed!(100.0);
+        telemetry.battery.remaining = 90;
+        history.push(1000, &telemetry, 0);
+        telemetry.set_available(Quantity::Altitude, false);
+        history.push(2000, &telemetry, 0);
+        assert_eq!(None, history.predict(altitude, 1));
+        assert_eq!(Trend::Steady, history.trend(altitude, 1, 1));
+        assert_eq!(Some(85), history.predict(battery, 1));
+
+        // history of altitude restarts after missing
+        telemetry.set_available(Quantity::Altitude, true);
+        telemetry.altitude = fixed!(101.0);
+        history.push(3000, &telemetry, 0);
+        assert_eq!(None, history.predict(altitude, 1));
+        telemetry.altitude = fixed!(102.0);
+        telemetry.set_updated(Quantity::Altitude, 3000);
+        history.push(4000, &telemetry, 2000);
+        assert_eq!(Some(1030), history.predict(altitude, 1));
+
+        // stale
+        history.push(5000, &telemetry, 1000);
+        assert_eq!(None, history.predict(altitude, 1));
+    }
+}
@@ -1,25 +1,76 @@
+use core::ops::Range;
+
 use enum_map::{enum_map, Enum, EnumMap};
+use fixed_point::FixedPoint;
 
+use crate::alert::{Alerts, Level};
 use crate::altitude::Altitude;
-use crate::aoa::AOA;
+use crate::aoa::{AOAIndexer, AOA};
+use crate::attribute::{mark, Attribute, Color};
+use crate::bar_graph::BarGraph;
 use crate::battery::Battery;
-use crate::drawable::{Align, Drawable};
+use crate::canvas::Canvas;
+use crate::clock::{Blink, Clock};
+use crate::drawable::{Drawable, NumOfLine};
+use crate::esc::Esc;
+use crate::flight_mode::FlightMode;
 use crate::g_force::GForce;
+use crate::gnss::Gnss;
 use crate::heading_tape::HeadingTape;
 use crate::height::Height;
-use crate::note::note;
+use crate::history::{History, Sample};
+use crate::link::Link;
+use crate::note::{columns, note, Notes, Slot};
 use crate::pitch_ladder::Pitchladder;
 use crate::rssi::RSSI;
+use crate::sideslip::Sideslip;
 use crate::speed::Speed;
 use crate::speed_vector::SpeedVector;
 use crate::steerpoint::Steerpoint;
 use crate::steerpoint_vector::SteerpointVector;
 use crate::symbol::SymbolTable;
-use crate::telemetry::Telemetry;
+use crate::telemetry::{FlightMode as FlightModeKind, Quantity, Telemetry};
+use crate::throttle::Throttle;
+use crate::timer::Timers;
+use crate::unit::Units;
 use crate::vario::Vario;
+use crate::wind::Wind;
 use crate::{AspectRatio, PixelRatio};
 
-#[derive(Enum)]
+pub use crate::aoa::AOAThresholds;
+pub use crate::battery::BatteryDisplay;
+pub use crate::drawable::Align;
+pub use crate::esc::EscDisplay;
+pub use crate::gnss::{CoordinateFormat, GnssDisplay};
+pub use crate::link::{LinkDisplay, LinkThresholds};
+pub use crate::throttle::ThrottleStyle;
+
+pub const MAX_BAR_GRAPHS: usize = 4;
+pub const MAX_WIDGETS: usize = 8;
+
+const PLACEHOLDER: &str = "---";
+const ALTITUDE_TREND: i32 = 200; // in 0.1 m, predicted change shown as trend arrow
+const SPEED_TREND: i32 = 50; // in 0.1 m/s
+const BATTERY_TREND: i32 = 5; // in percentage
+
+/// How a widget is drawn on a frame, depending on its telemetry quantities
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum State {
+    Normal,
+    Stale, // blinks as a whole
+    Placeholder,
+    Hidden,
+}
+
+/// How a widget is drawn when any of its telemetry quantities unavailable
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Unavailable {
+    #[default]
+    Placeholder, // "---" in place of the widget, center ones are hidden
+    Hide,
+}
+
+#[derive(Copy, Clone, Enum)]
 pub enum Displayable {
     // Bottom
     Pitchladder,
@@ -27,12 +78,16 @@ pub enum Displayable {
     // Center
     SpeedVector,
     SteerpointVector,
+    AOAIndexer,
 
     // TopLeft
     RSSI,
+    Link,
+    Timers,
 
     // Top
     HeadingTape,
+    Sideslip,
 
     // TopRight,
     Battery,
@@ -41,90 +96,381 @@ pub enum Displayable {
     Speed,
     AOA,
     GForce,
+    FlightMode,
+    Throttle,
+    Wind,
 
     // Right
     Altitude,
     Vario,
+    Esc,
+
+    // BottomLeft
+    Gnss,
 
     // BottomRight
     Steerpoint,
     Height,
 }
 
-pub struct HUD {
+/// Custom widgets are borrowed for 'a and drawn on rows of type B after built-in ones
+pub struct HUD<'a, B: AsMut<[u8]>> {
+    alerts: Alerts,
+    notes: Notes,
     altitude: Altitude,
     aoa: AOA,
+    aoa_indexer: AOAIndexer,
     battery: Battery,
+    esc: Esc,
+    flight_mode: FlightMode,
     g_force: GForce,
+    gnss: Gnss,
     heading_tape: HeadingTape,
     height: Height,
+    link: Link,
     pitch_ladder: Pitchladder,
     rssi: RSSI,
+    sideslip: Sideslip,
     speed: Speed,
     vario: Vario,
+    wind: Wind,
     speed_vector: SpeedVector,
     steerpoint: Steerpoint,
     steerpoint_vector: SteerpointVector,
+    throttle: Throttle,
+    timers: Timers,
+    bar_graphs: [Option<BarGraph>; MAX_BAR_GRAPHS], // slot of a removed one left empty
+    widgets: [Option<&'a (dyn Drawable<B> + Sync)>; MAX_WIDGETS], // removed slot left empty
+    blink: Blink,
+    unavailable: Unavailable,
+    stale_timeout: u32,
+    history: History,
+    trend_horizon: u8,
     aligns: EnumMap<Displayable, Option<Align>>,
 }
 
-impl HUD {
+impl<'a, B: AsMut<[u8]>> HUD<'a, B> {
     pub fn new(symbols: &SymbolTable, fov: u8, pixel: PixelRatio, aspect: AspectRatio) -> Self {
         let fov = core::cmp::max(10, fov); // avoid divide zero
         HUD {
-            altitude: Altitude::default(),
+            alerts: Alerts::default(),
+            notes: Notes::default(),
+            altitude: Altitude::new(symbols),
             aoa: AOA::new(&symbols),
+            aoa_indexer: AOAIndexer::new(symbols),
             battery: Battery::new(&symbols),
+            esc: Esc::default(),
+            flight_mode: FlightMode::default(),
             g_force: GForce::new(&symbols),
+            gnss: Gnss::new(symbols),
             heading_tape: HeadingTape::new(&symbols),
             height: Height::default(),
+            link: Link::default(),
             pitch_ladder: Pitchladder::new(&symbols, fov, pixel, aspect),
             rssi: RSSI::new(&symbols),
-            speed: Speed::default(),
+            sideslip: Sideslip::new(symbols),
+            speed: Speed::new(symbols),
             vario: Vario::default(),
+            wind: Wind::new(symbols),
             speed_vector: SpeedVector::new(&symbols, fov, aspect),
             steerpoint_vector: SteerpointVector::new(&symbols, fov, aspect),
+            throttle: Throttle::new(symbols),
+            timers: Timers::new(symbols),
             steerpoint: Steerpoint::new(&symbols),
+            bar_graphs: Default::default(),
+            widgets: [None; MAX_WIDGETS],
+            blink: Blink::default(),
+            unavailable: Unavailable::default(),
+            stale_timeout: 0,
+            history: History::default(),
+            trend_horizon: 10,
             aligns: enum_map! {
                 Displayable::Altitude => Some(Align::Right),
                 Displayable::AOA => Some(Align::Left),
+                Displayable::AOAIndexer => None,
                 Displayable::Battery => Some(Align::TopRight),
+                Displayable::Esc => None,
+                Displayable::FlightMode => None,
                 Displayable::GForce => Some(Align::Left),
+                Displayable::Gnss => None,
                 Displayable::HeadingTape => Some(Align::Top),
                 Displayable::Height => Some(Align::Bottom),
+                Displayable::Link => None,
                 Displayable::Pitchladder => Some(Align::Center),
                 Displayable::RSSI => Some(Align::TopLeft),
+                Displayable::Sideslip => None,
                 Displayable::Speed => Some(Align::Left),
                 Displayable::Vario => Some(Align::Right),
+                Displayable::Wind => None,
                 Displayable::SpeedVector => Some(Align::Center),
                 Displayable::Steerpoint => Some(Align::BottomRight),
                 Displayable::SteerpointVector => Some(Align::Center),
+                Displayable::Throttle => None,
+                Displayable::Timers => None,
             },
         }
     }
 
-    fn to_drawable<'b, B: AsMut<[u8]>>(&'b self, displayable: Displayable) -> &'b dyn Drawable<B> {
+    fn to_drawable(&self, displayable: Displayable) -> &dyn Drawable<B> {
         match displayable {
             Displayable::Altitude => &self.altitude,
             Displayable::AOA => &self.aoa,
+            Displayable::AOAIndexer => &self.aoa_indexer,
             Displayable::Battery => &self.battery,
+            Displayable::Esc => &self.esc,
+            Displayable::FlightMode => &self.flight_mode,
             Displayable::GForce => &self.g_force,
+            Displayable::Gnss => &self.gnss,
             Displayable::HeadingTape => &self.heading_tape,
             Displayable::Height => &self.height,
+            Displayable::Link => &self.link,
             Displayable::Pitchladder => &self.pitch_ladder,
             Displayable::RSSI => &self.rssi,
+            Displayable::Sideslip => &self.sideslip,
             Displayable::Speed => &self.speed,
             Displayable::Vario => &self.vario,
+            Displayable::Wind => &self.wind,
             Displayable::SpeedVector => &self.speed_vector,
             Displayable::Steerpoint => &self.steerpoint,
             Displayable::SteerpointVector => &self.steerpoint_vector,
+            Displayable::Throttle => &self.throttle,
+            Displayable::Timers => &self.timers,
+        }
+    }
+
+    pub fn show(&mut self, displayable: Displayable) {
+        self.aligns[displayable] = Some(self.to_drawable(displayable).align());
+    }
+
+    pub fn hide(&mut self, displayable: Displayable) {
+        self.aligns[displayable] = None;
+    }
+
+    /// Returns index of the bar graph, valid until removed, or None if full
+    pub fn add_bar_graph(&mut self, bar_graph: BarGraph) -> Option<usize> {
+        let index = self.bar_graphs.iter().position(Option::is_none)?;
+        self.bar_graphs[index] = Some(bar_graph);
+        Some(index)
+    }
+
+    /// Indexes of other bar graphs unchanged
+    pub fn remove_bar_graph(&mut self, index: usize) {
+        if let Some(slot) = self.bar_graphs.get_mut(index) {
+            *slot = None;
         }
     }
 
-    pub fn draw<'b, B: AsMut<[u8]>>(
+    /// Register a custom widget stacked with built-in ones of the same align,
+    /// returns index of the widget, valid until removed, or None if full
+    pub fn add_widget(&mut self, widget: &'a (dyn Drawable<B> + Sync)) -> Option<usize> {
+        let index = self.widgets.iter().position(Option::is_none)?;
+        self.widgets[index] = Some(widget);
+        Some(index)
+    }
+
+    /// Indexes of other widgets unchanged
+    pub fn remove_widget(&mut self, index: usize) {
+        if let Some(slot) = self.widgets.get_mut(index) {
+            *slot = None;
+        }
+    }
+
+    /// Built-in widgets shown, followed by bar graphs and custom widgets
+    fn drawables(&self) -> impl Iterator<Item = (Align, &dyn Drawable<B>)> + '_ {
+        let displayables = self
+            .aligns
+            .iter()
+            .filter_map(|(display, align)| align.map(|align| (align, self.to_drawable(display))));
+        let bar_graphs = self.bar_graphs.iter().flatten().map(|bar_graph| {
+            let drawable: &dyn Drawable<B> = bar_graph;
+            (drawable.align(), drawable)
+        });
+        let widgets = self.widgets.iter().flatten().map(|&widget| {
+            let drawable: &dyn Drawable<B> = widget;
+            (drawable.align(), drawable)
+        });
+        displayables.chain(bar_graphs).chain(widgets)
+    }
+
+    fn state(&self, drawable: &dyn Drawable<B>, now: u32, telemetry: &Telemetry) -> State {
+        let quantities = drawable.quantities();
+        if !quantities.iter().all(|&q| telemetry.is_available(q)) {
+            return match self.unavailable {
+                Unavailable::Placeholder => State::Placeholder,
+                Unavailable::Hide => State::Hidden,
+            };
+        }
+        let timeout = self.stale_timeout;
+        match quantities
+            .iter()
+            .any(|&q| telemetry.is_stale(q, now, timeout))
+        {
+            true => State::Stale,
+            false => State::Normal,
+        }
+    }
+
+    pub fn alerts(&mut self) -> &mut Alerts {
+        &mut self.alerts
+    }
+
+    pub fn notes(&mut self) -> &mut Notes {
+        &mut self.notes
+    }
+
+    pub fn timers(&mut self) -> &mut Timers {
+        &mut self.timers
+    }
+
+    /// Telemetry is always in SI units, converted by each widget on drawing
+    pub fn set_units(&mut self, units: Units) {
+        self.altitude.set_unit(units.altitude);
+        self.height.set_unit(units.altitude);
+        self.vario.set_unit(units.vertical_speed);
+        self.speed.set_unit(units.speed);
+        self.wind.set_unit(units.speed);
+        self.steerpoint.set_unit(units.distance);
+        self.esc.set_unit(units.temperature);
+    }
+
+    pub fn set_aoa_thresholds(&mut self, thresholds: AOAThresholds) {
+        self.aoa.set_thresholds(thresholds);
+        self.aoa_indexer.set_thresholds(thresholds);
+    }
+
+    pub fn set_battery_display(&mut self, display: BatteryDisplay) {
+        self.battery.set_display(display);
+    }
+
+    pub fn set_battery_warning(&mut self, percentage: u8) {
+        self.battery.set_warning(percentage);
+    }
+
+    pub fn set_battery_reserve(&mut self, percentage: u8) {
+        self.battery.set_reserve(percentage);
+        self.steerpoint.set_reserve(percentage);
+    }
+
+    pub fn set_esc_display(&mut self, display: EscDisplay) {
+        self.esc.set_display(display);
+    }
+
+    /// Raise ESC over temperature warning above limit in celsius
+    pub fn set_esc_temperature_limit(&mut self, limit: i16) {
+        self.esc.set_temperature_limit(limit);
+    }
+
+    pub fn set_throttle_style(&mut self, style: ThrottleStyle) {
+        self.throttle.set_style(style);
+    }
+
+    pub fn set_link_display(&mut self, display: LinkDisplay) {
+        self.link.set_display(display);
+    }
+
+    pub fn set_link_thresholds(&mut self, thresholds: LinkThresholds) {
+        self.link.set_thresholds(thresholds);
+    }
+
+    pub fn set_gnss_display(&mut self, display: GnssDisplay) {
+        self.gnss.set_display(display);
+    }
+
+    /// Custom name of flight mode, e.g. "MAN" for ACRO
+    pub fn set_flight_mode_name(&mut self, mode: FlightModeKind, name: &'static str) {
+        self.flight_mode.set_name(mode, name);
+    }
+
+    pub fn set_g_force_limit(&mut self, positive: FixedPoint<i8, 1>, negative: FixedPoint<i8, 1>) {
+        self.g_force.set_limit(positive, negative);
+    }
+
+    /// Show session max and min G beside the live value
+    pub fn set_g_force_extremum(&mut self, show: bool) {
+        self.g_force.set_show_extremum(show);
+    }
+
+    pub fn reset_g_force(&mut self) {
+        self.g_force.reset();
+    }
+
+    /// Blink rate and duty cycle shared by all widgets
+    pub fn set_blink(&mut self, blink: Blink) {
+        self.blink = blink;
+    }
+
+    /// Policy shared by all widgets including custom ones
+    pub fn set_unavailable(&mut self, policy: Unavailable) {
+        self.unavailable = policy;
+    }
+
+    /// Widgets with any quantity not updated within timeout in milliseconds blink,
+    /// "TELEMETRY LOST" raised if all stamped quantities stale, 0 disables
+    pub fn set_stale_timeout(&mut self, timeout: u32) {
+        self.stale_timeout = timeout;
+    }
+
+    /// Seconds ahead of trends predicted from history
+    pub fn set_trend_horizon(&mut self, seconds: u8) {
+        self.trend_horizon = seconds;
+    }
+
+    /// Recent telemetry samples recorded on update
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Update widget states, timers and notes, raise or clear alerts derived from telemetry,
+    /// supposed to be called on telemetry received, timestamps are milliseconds
+    pub fn update(&mut self, now: u32, telemetry: &Telemetry) {
+        self.notes.update(now);
+        self.timers.update(now, telemetry.armed);
+        self.battery.update(telemetry);
+        self.flight_mode.update(now, telemetry);
+        let timeout = self.stale_timeout;
+        let valid = |quantity| {
+            telemetry.is_available(quantity) && !telemetry.is_stale(quantity, now, timeout)
+        };
+        if valid(Quantity::GForce) {
+            self.g_force.update(telemetry);
+        }
+        self.history.push(now, telemetry, timeout);
+        let (history, seconds) = (&self.history, self.trend_horizon as u32);
+        let altitude = history.trend(|s: &Sample| Some(s.altitude?.0), seconds, ALTITUDE_TREND);
+        self.altitude.set_trend(altitude);
+        let speed = history.trend(|s: &Sample| Some(s.speed?.0.into()), seconds, SPEED_TREND);
+        self.speed.set_trend(speed);
+        let battery = history.trend(|s: &Sample| Some(s.battery?.into()), seconds, BATTERY_TREND);
+        self.battery.set_trend(battery);
+        let low_link = self.aligns[Displayable::Link].is_some()
+            && valid(Quantity::Link)
+            && self.link.is_low(telemetry);
+        self.alerts.set(Level::Caution, "LOW LINK", low_link);
+        let over_temperature = valid(Quantity::Esc) && self.esc.is_over_temperature(telemetry);
+        self.alerts
+            .set(Level::Warning, "ESC OVERTEMP", over_temperature);
+        let mut stamped = (0..Quantity::LENGTH)
+            .map(Quantity::from_usize)
+            .filter(|&quantity| telemetry.is_stamped(quantity))
+            .peekable();
+        let lost = stamped.peek().is_some()
+            && stamped.all(|quantity| telemetry.is_stale(quantity, now, timeout));
+        self.alerts.set(Level::Caution, "TELEMETRY LOST", lost);
+    }
+
+    /// Animations are derived from now, which is milliseconds from an arbitrary epoch
+    pub fn draw<'b>(&self, now: u32, telemetry: &Telemetry<'b>, output: &'b mut [B]) -> &'b [B] {
+        self.draw_with_attributes(now, telemetry, output, &mut [])
+    }
+
+    /// Draw with an attribute plane parallel to output, so that driver may apply blink,
+    /// invert or color natively, blinking elements are always drawn in this case
+    pub fn draw_with_attributes<'b>(
         &self,
+        now: u32,
         telemetry: &Telemetry<'b>,
         output: &'b mut [B],
+        attributes: &mut [B],
     ) -> &'b [B] {
         output.iter_mut().for_each(|line| {
             for ch in line.as_mut() {
@@ -134,51 +480,156 @@ impl HUD {
                 }
             }
         });
+        attributes.iter_mut().for_each(|line| line.as_mut().fill(0));
+        let mut clock = Clock::new(now, self.blink);
+        clock.native_blink = !attributes.is_empty();
         let output_len = output.len();
+
         let mut indexes: EnumMap<Align, usize> = EnumMap::default();
-        for (display, align_option) in self.aligns.iter() {
-            let align = match align_option {
-                Some(align) => *align,
-                None => continue,
-            };
-            let drawable: &dyn Drawable<B> = self.to_drawable(display);
-            let region = match align {
-                Align::Top | Align::TopLeft | Align::TopRight => &mut output[indexes[align]..],
-                Align::Bottom | Align::BottomLeft | Align::BottomRight => {
-                    #[cfg(test)]
-                    println!("{}", indexes[align]);
-                    &mut output[..output_len - indexes[align]]
+        for (align, drawable) in self.drawables() {
+            let range = region(align, indexes[align], output_len);
+            let region = rows(output, range.clone());
+            let attributes = rows(attributes, range);
+            let state = self.state(drawable, now, telemetry);
+            let lines = match state {
+                // graphical widgets spread over the whole region
+                State::Stale if align == Align::Center && !clock.phase() => 0,
+                State::Normal | State::Stale => {
+                    drawable.draw(telemetry, &clock, region, attributes)
                 }
-                Align::Left | Align::Right => &mut output[output_len / 2 + indexes[align]..],
-                _ => output,
+                State::Placeholder => placeholder(align, region),
+                State::Hidden => 0,
             };
-            indexes[align] += drawable.draw(&telemetry, region);
+            if state == State::Stale && align != Align::Center {
+                blink(align, lines, &clock, region, attributes);
+            }
+            indexes[align] += lines;
         }
 
         indexes[Align::Center] = 2;
-        let region = &mut output[output_len / 2 + indexes[Align::Left]..];
-        indexes[Align::Left] += note(telemetry.notes.left, Align::Left, region);
-        let region = &mut output[output_len / 2 + indexes[Align::Center]..];
-        indexes[Align::Center] += note(telemetry.notes.center, Align::Center, region);
-        let region = &mut output[output_len / 2 + indexes[Align::Right]..];
-        indexes[Align::Right] += note(telemetry.notes.right, Align::Right, region);
+        let index = output_len / 2 + indexes[Align::Center];
+        let stall = self.aligns[Displayable::AOA].is_some() && self.aoa.is_stall(telemetry);
+        if let (true, Some(line)) = (stall, output.get_mut(index)) {
+            let width = line.as_mut().len();
+            let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+            mark(
+                attributes,
+                index,
+                columns(5, Align::Center, width),
+                attribute,
+            );
+            indexes[Align::Center] += note("STALL", Align::Center, rows(output, index..output_len));
+        }
+        let range = output_len / 2 + indexes[Align::Center]..output_len;
+        let region = rows(output, range.clone());
+        indexes[Align::Center] += self.alerts.draw(&clock, region, rows(attributes, range));
+        let region = rows(output, output_len / 2 + indexes[Align::Left]..output_len);
+        indexes[Align::Left] += self.notes.draw(Slot::Left, region);
+        let region = rows(output, output_len / 2 + indexes[Align::Center]..output_len);
+        indexes[Align::Center] += self.notes.draw(Slot::Center, region);
+        let region = rows(output, output_len / 2 + indexes[Align::Right]..output_len);
+        indexes[Align::Right] += self.notes.draw(Slot::Right, region);
         output
     }
 }
 
+/// Single line placeholder aligned as the widget it replaces
+fn placeholder<B: AsMut<[u8]>>(align: Align, output: &mut [B]) -> NumOfLine {
+    let mut canvas = Canvas::new(output);
+    if align == Align::Center || canvas.height() == 0 {
+        return 0;
+    }
+    let row = match align {
+        Align::Bottom | Align::BottomLeft | Align::BottomRight => canvas.height() - 1,
+        _ => 0,
+    };
+    let width = canvas.width();
+    match align {
+        Align::TopLeft | Align::Left | Align::BottomLeft => {
+            canvas.text(row, 0, PLACEHOLDER, Align::Left)
+        }
+        Align::TopRight | Align::Right | Align::BottomRight => {
+            canvas.text(row, width, PLACEHOLDER, Align::Right)
+        }
+        _ => canvas.text(row, width / 2, PLACEHOLDER, Align::Center),
+    };
+    1
+}
+
+/// Rows of output for the widget stacked after index lines on its align
+fn region(align: Align, index: usize, output_len: usize) -> Range<usize> {
+    match align {
+        Align::Top | Align::TopLeft | Align::TopRight => index..output_len,
+        Align::Bottom | Align::BottomLeft | Align::BottomRight => {
+            0..output_len.saturating_sub(index)
+        }
+        Align::Left | Align::Right => output_len / 2 + index..output_len,
+        _ => 0..output_len,
+    }
+}
+
+/// Stale widget blinks over lines it drew, on its side of output
+fn blink<B: AsMut<[u8]>>(
+    align: Align,
+    lines: NumOfLine,
+    clock: &Clock,
+    output: &mut [B],
+    attributes: &mut [B],
+) {
+    let rows = match align {
+        Align::Bottom | Align::BottomLeft | Align::BottomRight => {
+            output.len().saturating_sub(lines)..output.len()
+        }
+        _ => 0..lines.min(output.len()),
+    };
+    for row in rows {
+        let line = output[row].as_mut();
+        let width = line.len();
+        let columns = match align {
+            Align::TopLeft | Align::Left | Align::BottomLeft => 0..width / 2,
+            Align::TopRight | Align::Right | Align::BottomRight => width / 2..width,
+            _ => 0..width,
+        };
+        if clock.native_blink {
+            mark(attributes, row, columns, Attribute::BLINK);
+        } else if !clock.is_on() {
+            line[columns]
+                .iter_mut()
+                .filter(|ch| **ch != 0)
+                .for_each(|ch| *ch = b' ');
+        }
+    }
+}
+
+/// Clipped rows of output or attribute plane
+fn rows<B>(rows: &mut [B], range: Range<usize>) -> &mut [B] {
+    let end = range.end.min(rows.len());
+    let start = range.start.min(end);
+    &mut rows[start..end]
+}
+
 #[cfg(test)]
 mod test {
+    use enum_map::{enum_map, Enum};
     use fixed_point::fixed;
 
-    use super::HUD;
+    use super::{Displayable, Unavailable, HUD, MAX_BAR_GRAPHS};
+    use crate::alert::Level;
+    use crate::attribute::{Attribute, Color};
+    use crate::bar_graph::{BarGraph, Source};
+    use crate::canvas::Canvas;
+    use crate::clock::Clock;
+    use crate::drawable::{Align, Drawable, NumOfLine};
     use crate::symbol::default_symbol_table;
-    use crate::telemetry::{Attitude, Notes, SphericalCoordinate, Steerpoint, Telemetry};
+    use crate::telemetry::{
+        Attitude, FlightMode, Quantity, SphericalCoordinate, Steerpoint, Telemetry,
+    };
     use crate::test_utils::{fill_edge, to_utf8_string};
     use crate::{AspectRatio, PixelRatio};
 
     fn default_telemetry() -> Telemetry<'static> {
         Telemetry {
-            altitude: 1000,
+            altitude: fixed!(304.8),
             attitude: Attitude {
                 pitch: 10,
                 roll: 10,
@@ -186,21 +637,18 @@ mod test {
             heading: 10,
             aoa: fixed!(3.1),
             g_force: fixed!(1.1),
-            height: 99,
-            notes: Notes {
-                left: "MAN",
-                ..Default::default()
-            },
+            height: fixed!(30.2),
             rssi: 100,
-            vario: 100,
+            vario: fixed!(0.51),
             speed_vector: SphericalCoordinate {
-                rho: 100, // speed
+                rho: fixed!(51.4), // speed
                 theta: 10,
                 phi: -5,
             },
+            ground_speed: fixed!(51.4),
             steerpoint: Steerpoint {
                 coordinate: SphericalCoordinate {
-                    rho: 47,
+                    rho: 8_704,
                     theta: -10,
                     phi: -14,
                 },
@@ -215,9 +663,14 @@ mod test {
         let mut buffer = [[0u8; 30]; 16];
         let symbols = default_symbol_table();
         let px_ratio = pixel_ratio!(16:30);
-        let hud = HUD::new(&symbols, 150, px_ratio, aspect_ratio!(16:9));
-        let telemetry = default_telemetry();
-        hud.draw(&telemetry, &mut buffer);
+        let mut hud = HUD::new(&symbols, 150, px_ratio, aspect_ratio!(16:9));
+        hud.show(Displayable::FlightMode);
+        hud.set_flight_mode_name(FlightMode::Acro, "MAN");
+        let telemetry = Telemetry {
+            armed: true,
+            ..default_telemetry()
+        };
+        hud.draw(0, &telemetry, &mut buffer);
         fill_edge(&mut buffer);
 
         let expected = "⏉100    000 . 010 . 020   β100\
@@ -228,14 +681,400 @@ mod test {
                         ▔⎺⎺⎻⎻─⎼⎼⎽⎽▁                  .\
                         .          ▔▔⎺⎺⎻──⎼⎼⎽▁▁      .\
                         .                      ▔▔⎺⎻⎻──\
-                        . 100                     1000\
-                        ⍺  ⒊1            ⏂         100\
+                        . 100 KT                1000FT\
+                        ⍺  ⒊1            ⏂      100FPM\
                         G  ⒈1                        .\
                         MAN          ☐               .\
                         .                            .\
                         .                       0/HOME\
                         .                         ⒋7NM\
-                        .             99      00:02:49";
+                        .            99FT     00:02:49";
         assert_eq!(expected, to_utf8_string(&buffer));
     }
+
+    #[test]
+    fn test_show_and_hide() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        let telemetry = default_telemetry();
+        hud.show(Displayable::Sideslip);
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "              [●]             ",
+            to_utf8_string(&buffer[2..3])
+        );
+
+        hud.hide(Displayable::Sideslip);
+        hud.hide(Displayable::Pitchladder);
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "                              ",
+            to_utf8_string(&buffer[2..3])
+        );
+    }
+
+    #[test]
+    fn test_stall() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        let telemetry = Telemetry {
+            aoa: fixed!(12.0),
+            ..default_telemetry()
+        };
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "G  ⒈1        STALL            ",
+            to_utf8_string(&buffer[10..11])
+        );
+    }
+
+    #[test]
+    fn test_stall_on_short_output() {
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        (0..Displayable::LENGTH).for_each(|i| hud.hide(Displayable::from_usize(i)));
+        hud.show(Displayable::AOA);
+        let telemetry = Telemetry {
+            aoa: fixed!(12.0),
+            ..default_telemetry()
+        };
+        for rows in 0..=4 {
+            let mut buffer = [[0u8; 30]; 4];
+            hud.draw(0, &telemetry, &mut buffer[..rows]);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_size() {
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        (0..Displayable::LENGTH).for_each(|i| hud.show(Displayable::from_usize(i)));
+        let telemetry = Telemetry {
+            aoa: fixed!(12.0),
+            motors: 4,
+            ..default_telemetry()
+        };
+        let mut unavailable = telemetry;
+        unavailable.set_available(Quantity::Altitude, false);
+        for rows in 0..=3 {
+            for columns in 0..=3 {
+                let mut buffer = std::vec![std::vec![0u8; columns]; rows];
+                let mut attributes = buffer.clone();
+                hud.draw(0, &telemetry, &mut buffer);
+                hud.draw(0, &unavailable, &mut buffer);
+                hud.draw_with_attributes(0, &telemetry, &mut buffer, &mut attributes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_low_link() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        hud.show(Displayable::Link);
+        let mut telemetry = default_telemetry();
+        telemetry.link.quality = 50;
+        hud.update(0, &telemetry);
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "G  ⒈1      LOW LINK           ",
+            to_utf8_string(&buffer[10..11])
+        );
+
+        telemetry.link.quality = 100;
+        hud.update(0, &telemetry);
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "G  ⒈1                         ",
+            to_utf8_string(&buffer[10..11])
+        );
+    }
+
+    #[test]
+    fn test_unavailable_warnings() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        hud.show(Displayable::Link);
+        hud.set_g_force_extremum(true);
+        let mut telemetry = default_telemetry();
+        telemetry.link.quality = 0;
+        telemetry.set_available(Quantity::Link, false);
+        telemetry.motors = 1;
+        telemetry.escs[0].temperature = 150;
+        telemetry.set_available(Quantity::Esc, false);
+        telemetry.g_force = fixed!(-12.8);
+        telemetry.set_available(Quantity::GForce, false);
+        hud.update(0, &telemetry);
+        assert!(!hud.alerts().is_active(Level::Caution));
+        assert!(!hud.alerts().is_active(Level::Warning));
+
+        telemetry.g_force = fixed!(1.1);
+        telemetry.set_available(Quantity::GForce, true);
+        hud.update(1000, &telemetry);
+        hud.draw(1000, &telemetry, &mut buffer);
+        assert_eq!(
+            "G  ⒈1 ⒈1/⒈1                   ",
+            to_utf8_string(&buffer[10..11])
+        );
+
+        // stale ones neither
+        hud.set_stale_timeout(500);
+        telemetry.set_available(Quantity::Esc, true);
+        telemetry.updated = enum_map! { _ => 2000 };
+        telemetry.set_updated(Quantity::Esc, 1000);
+        hud.update(2000, &telemetry);
+        assert!(!hud.alerts().is_active(Level::Warning));
+    }
+
+    #[test]
+    fn test_attributes() {
+        let mut buffer = [[0u8; 30]; 16];
+        let mut attributes = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        let telemetry = Telemetry {
+            aoa: fixed!(12.0),
+            ..default_telemetry()
+        };
+        // blinking elements drawn regardless of blink phase
+        hud.draw_with_attributes(250, &telemetry, &mut buffer, &mut attributes);
+        assert_eq!(
+            "⍺ 1⒉0            ⏂      100FPM",
+            to_utf8_string(&buffer[9..10])
+        );
+        assert_eq!(
+            "G  ⒈1        STALL            ",
+            to_utf8_string(&buffer[10..11])
+        );
+        let blink_red = (Attribute::BLINK | Attribute::color(Color::Red)).0;
+        assert_eq!([0, blink_red, blink_red], attributes[9][..3]);
+        assert_eq!([0, blink_red, blink_red], attributes[10][12..15]);
+    }
+
+    #[test]
+    fn test_unavailable() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        let mut telemetry = default_telemetry();
+        telemetry.set_available(Quantity::Altitude, false);
+        telemetry.set_available(Quantity::Height, false);
+        telemetry.set_available(Quantity::Speed, false);
+        telemetry.set_available(Quantity::GroundSpeed, false);
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "---                        ---",
+            to_utf8_string(&buffer[8..9])
+        );
+        assert_eq!(
+            "⍺  ⒊1                   100FPM",
+            to_utf8_string(&buffer[9..10])
+        );
+        assert_eq!(
+            "              ---     00:00:00",
+            to_utf8_string(&buffer[15..16])
+        );
+
+        hud.set_unavailable(Unavailable::Hide);
+        hud.draw(0, &telemetry, &mut buffer);
+        assert_eq!(
+            "⍺  ⒊1                   100FPM",
+            to_utf8_string(&buffer[8..9])
+        );
+        assert_eq!(
+            "                      00:00:00",
+            to_utf8_string(&buffer[15..16])
+        );
+    }
+
+    #[test]
+    fn test_stale() {
+        let mut buffer = [[0u8; 30]; 16];
+        let mut attributes = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        hud.set_stale_timeout(500);
+        let mut telemetry = default_telemetry();
+        telemetry.updated = enum_map! { _ => 1000 };
+        telemetry.set_updated(Quantity::Altitude, 400);
+        hud.update(1000, &telemetry);
+        hud.draw(1000, &telemetry, &mut buffer);
+        assert_eq!(
+            "  100 KT                1000FT",
+            to_utf8_string(&buffer[8..9])
+        );
+        hud.draw(1250, &telemetry, &mut buffer);
+        assert_eq!(
+            "  100 KT                      ",
+            to_utf8_string(&buffer[8..9])
+        );
+
+        hud.draw_with_attributes(1250, &telemetry, &mut buffer, &mut attributes);
+        assert_eq!(
+            "  100 KT                1000FT",
+            to_utf8_string(&buffer[8..9])
+        );
+        assert_eq!([Attribute::BLINK.0; 15], attributes[8][15..]);
+        assert_eq!([0; 15], attributes[8][..15]);
+        assert!(!hud.alerts().is_active(Level::Caution));
+
+        hud.update(1500, &telemetry);
+        assert!(hud.alerts().is_active(Level::Caution));
+        hud.draw(1500, &telemetry, &mut buffer);
+        assert_eq!(
+            "G  ⒈1   TELEMETRY LOST        ",
+            to_utf8_string(&buffer[10..11])
+        );
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        let mut telemetry = default_telemetry();
+        hud.update(0, &telemetry);
+        telemetry.altitude = fixed!(314.8);
+        telemetry.battery.remaining = 90;
+        hud.update(1000, &telemetry);
+        assert_eq!(
+            Some(80),
+            hud.history().predict(|s| Some(s.battery?.into()), 1)
+        );
+        hud.draw(1000, &telemetry, &mut buffer);
+        assert!(to_utf8_string(&buffer[0..1]).ends_with("β90↓"));
+        assert_eq!(
+            "  100 KT               ↑1033FT",
+            to_utf8_string(&buffer[8..9])
+        );
+    }
+
+    struct Widget;
+
+    impl<T: AsMut<[u8]>> Drawable<T> for Widget {
+        fn align(&self) -> Align {
+            Align::TopLeft
+        }
+
+        fn draw(&self, _: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+            Canvas::new(output).text(0, 0, "WIDGET", Align::Left);
+            1
+        }
+    }
+
+    #[test]
+    fn test_widget() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let widget = Widget;
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        assert_eq!(Some(0), hud.add_widget(&widget));
+        hud.draw(0, &default_telemetry(), &mut buffer);
+        assert_eq!(
+            "WIDGET   ╵     ^              ",
+            to_utf8_string(&buffer[1..2])
+        );
+
+        let other = Widget;
+        assert_eq!(Some(1), hud.add_widget(&other));
+        hud.remove_widget(0);
+        hud.draw(0, &default_telemetry(), &mut buffer);
+        assert_eq!(
+            "WIDGET   ╵     ^              ",
+            to_utf8_string(&buffer[1..2])
+        );
+        hud.remove_widget(1);
+        hud.draw(0, &default_telemetry(), &mut buffer);
+        assert_eq!(
+            "         ╵     ^              ",
+            to_utf8_string(&buffer[1..2])
+        );
+    }
+
+    struct Tall;
+
+    impl<T: AsMut<[u8]>> Drawable<T> for Tall {
+        fn align(&self) -> Align {
+            Align::TopRight
+        }
+
+        fn quantities(&self) -> &[Quantity] {
+            &[Quantity::Altitude]
+        }
+
+        fn draw(&self, _: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+            let mut canvas = Canvas::new(output);
+            for row in 0..10 {
+                canvas.text(row, 70, "X", Align::Left);
+            }
+            10
+        }
+    }
+
+    #[test]
+    fn test_stale_tall_widget() {
+        let mut buffer = [[0u8; 80]; 20];
+        let mut attributes = [[0u8; 80]; 20];
+        let symbols = default_symbol_table();
+        let tall = Tall;
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        (0..Displayable::LENGTH).for_each(|i| hud.hide(Displayable::from_usize(i)));
+        hud.add_widget(&tall);
+        hud.set_stale_timeout(500);
+        let mut telemetry = default_telemetry();
+        telemetry.updated = enum_map! { _ => 1000 };
+        telemetry.set_updated(Quantity::Altitude, 400);
+        hud.draw(1000, &telemetry, &mut buffer);
+        assert!(buffer[..10].iter().all(|line| line[70] == b'X'));
+        hud.draw(1250, &telemetry, &mut buffer);
+        assert!(buffer[..10].iter().all(|line| line[70] == b' '));
+
+        hud.draw_with_attributes(1250, &telemetry, &mut buffer, &mut attributes);
+        assert!(buffer[..10].iter().all(|line| line[70] == b'X'));
+        assert!(attributes[..10]
+            .iter()
+            .all(|line| line[70] == Attribute::BLINK.0));
+        assert!(attributes.iter().all(|line| line[..40] == [0; 40]));
+        assert!(attributes[10..].iter().all(|line| line[40..] == [0; 40]));
+    }
+
+    #[test]
+    fn test_bar_graph() {
+        let mut buffer = [[0u8; 30]; 16];
+        let symbols = default_symbol_table();
+        let mut hud = HUD::new(&symbols, 150, pixel_ratio!(16:30), aspect_ratio!(16:9));
+        let bar_graph = |source: Source| {
+            let mut bar_graph = BarGraph::new(&symbols, source, 0, 100);
+            bar_graph.set_length(4);
+            bar_graph.set_align(Align::TopLeft);
+            bar_graph
+        };
+        let full = hud.add_bar_graph(bar_graph(|_| 100)).unwrap();
+        let half = hud.add_bar_graph(bar_graph(|_| 50)).unwrap();
+        for _ in 2..MAX_BAR_GRAPHS {
+            assert!(hud.add_bar_graph(bar_graph(|_| 0)).is_some());
+        }
+        assert_eq!(None, hud.add_bar_graph(bar_graph(|_| 0)));
+        hud.draw(0, &default_telemetry(), &mut buffer);
+        assert_eq!("───⎪", to_utf8_string(&[&buffer[1][..4]]));
+        assert_eq!("──▏ ", to_utf8_string(&[&buffer[2][..4]]));
+
+        hud.remove_bar_graph(full);
+        hud.draw(0, &default_telemetry(), &mut buffer);
+        assert_eq!("──▏ ", to_utf8_string(&[&buffer[1][..4]]));
+        hud.remove_bar_graph(half);
+        hud.draw(0, &default_telemetry(), &mut buffer);
+        assert_eq!("▏   ", to_utf8_string(&[&buffer[1][..4]]));
+        assert_eq!(Some(full), hud.add_bar_graph(bar_graph(|_| 100)));
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<HUD<[u8; 32]>>();
+    }
 }
@@ -53,24 +53,42 @@ impl AspectRatio {
 #[cfg(test)]
 mod test_utils;
 
+pub mod alert;
 mod altitude;
 mod aoa;
+pub mod attribute;
+pub mod bar_graph;
 mod battery;
-mod drawable;
+pub mod canvas;
+pub mod clock;
+pub mod drawable;
+mod esc;
+pub mod estimator;
+pub mod filter;
+mod flight_mode;
+pub mod format;
 mod g_force;
+mod gnss;
 mod heading_tape;
 mod height;
+pub mod history;
 pub mod hud;
-mod note;
+mod link;
+pub mod note;
 mod pitch_ladder;
 mod rssi;
+mod sideslip;
 mod speed;
 mod speed_vector;
 mod steerpoint;
 mod steerpoint_vector;
 pub mod symbol;
 pub mod telemetry;
+mod throttle;
+pub mod timer;
+pub mod unit;
 mod vario;
+mod wind;
 
 extern crate micromath;
 
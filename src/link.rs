@@ -0,0 +1,219 @@
+use core::fmt::Write;
+
+use heapless::String;
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::telemetry::{Quantity, Telemetry};
+
+#[derive(Copy, Clone, Debug)]
+pub struct LinkDisplay {
+    pub quality: bool,
+    pub uplink_rssi: bool,
+    pub downlink_rssi: bool,
+    pub snr: bool,
+    pub rf_mode: bool,
+    pub tx_power: bool,
+}
+
+impl Default for LinkDisplay {
+    fn default() -> Self {
+        Self {
+            quality: true,
+            uplink_rssi: true,
+            downlink_rssi: false,
+            snr: false,
+            rf_mode: false,
+            tx_power: false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct LinkThresholds {
+    pub quality: u8,        // percentage
+    pub uplink_rssi: i16,   // in dBm
+    pub downlink_rssi: i16, // in dBm
+    pub snr: i8,            // in dB
+}
+
+impl Default for LinkThresholds {
+    fn default() -> Self {
+        Self {
+            quality: 70,
+            uplink_rssi: -100,
+            downlink_rssi: -100,
+            snr: i8::MIN,
+        }
+    }
+}
+
+impl LinkThresholds {
+    pub fn is_low(&self, telemetry: &Telemetry) -> bool {
+        let link = &telemetry.link;
+        link.quality < self.quality
+            || link.uplink_rssi < self.uplink_rssi
+            || link.downlink_rssi < self.downlink_rssi
+            || link.snr < self.snr
+    }
+}
+
+#[derive(Default)]
+pub struct Link {
+    display: LinkDisplay,
+    thresholds: LinkThresholds,
+}
+
+impl Link {
+    pub fn set_display(&mut self, display: LinkDisplay) {
+        self.display = display;
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: LinkThresholds) {
+        self.thresholds = thresholds;
+    }
+
+    pub fn is_low(&self, telemetry: &Telemetry) -> bool {
+        self.thresholds.is_low(telemetry)
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Link {
+    fn align(&self) -> Align {
+        Align::TopLeft
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Link]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let link = &telemetry.link;
+        let low = self.is_low(telemetry);
+        let visible = !low || clock.is_on();
+
+        let display = &self.display;
+        let mut lines: [String<8>; 6] = Default::default();
+        if display.quality {
+            write!(lines[0], "LQ{}", link.quality).ok();
+        }
+        if display.uplink_rssi {
+            write!(lines[1], "U{}DBM", link.uplink_rssi).ok();
+        }
+        if display.downlink_rssi {
+            write!(lines[2], "D{}DBM", link.downlink_rssi).ok();
+        }
+        if display.snr {
+            write!(lines[3], "SNR{}", link.snr).ok();
+        }
+        if display.rf_mode {
+            write!(lines[4], "{:.8}", link.rf_mode).ok();
+        }
+        if display.tx_power {
+            write!(lines[5], "{}MW", link.tx_power).ok();
+        }
+        let mut canvas = Canvas::new(output);
+        let width = canvas.width();
+        let mut index = 0;
+        for line in lines.iter().filter(|line| !line.is_empty()) {
+            if index >= canvas.height() {
+                break;
+            }
+            if visible {
+                canvas.text(index, 0, line.as_str(), Align::Left);
+            }
+            if low {
+                let attribute = Attribute::BLINK | Attribute::color(Color::Amber);
+                mark(attributes, index, 0..line.len().min(width), attribute);
+            }
+            index += 1;
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::telemetry::{Link as LinkStatistics, Telemetry};
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
+
+    use super::{Link, LinkDisplay};
+
+    #[test]
+    fn test_link() {
+        let mut buffer = [[0u8; 8]; 6];
+        let mut link = Link::default();
+        link.set_display(LinkDisplay {
+            quality: true,
+            uplink_rssi: true,
+            downlink_rssi: true,
+            snr: true,
+            rf_mode: true,
+            tx_power: true,
+        });
+        let telemetry = Telemetry {
+            link: LinkStatistics {
+                quality: 100,
+                uplink_rssi: -65,
+                downlink_rssi: -70,
+                snr: 9,
+                rf_mode: "500HZ",
+                tx_power: 250,
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            6,
+            link.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("LQ100   ", to_utf8_string(&buffer[0..1]));
+        assert_eq!("U-65DBM ", to_utf8_string(&buffer[1..2]));
+        assert_eq!("D-70DBM ", to_utf8_string(&buffer[2..3]));
+        assert_eq!("SNR9    ", to_utf8_string(&buffer[3..4]));
+        assert_eq!("500HZ   ", to_utf8_string(&buffer[4..5]));
+        assert_eq!("250MW   ", to_utf8_string(&buffer[5..6]));
+    }
+
+    #[test]
+    fn test_low_link() {
+        let mut buffer = [[0u8; 8]; 2];
+        let link = Link::default();
+        let mut telemetry = Telemetry {
+            link: LinkStatistics {
+                quality: 50,
+                uplink_rssi: -65,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(link.is_low(&telemetry));
+        assert_eq!(
+            2,
+            link.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("LQ50    U-65DBM ", to_utf8_string(&buffer));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        assert_eq!(2, link.draw(&telemetry, &blink_off(), &mut buffer, &mut []));
+        assert_eq!("                ", to_utf8_string(&buffer));
+
+        telemetry.link.quality = 90;
+        assert!(!link.is_low(&telemetry));
+        telemetry.link.uplink_rssi = -105;
+        assert!(link.is_low(&telemetry));
+
+        telemetry.link.uplink_rssi = -65;
+        telemetry.link.downlink_rssi = -105;
+        assert!(link.is_low(&telemetry));
+    }
+}
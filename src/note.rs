@@ -1,17 +1,241 @@
-use super::drawable::Align;
+use core::ops::Range;
+
+use enum_map::{Enum, EnumMap};
+use heapless::{String, Vec};
+
+use crate::canvas::Canvas;
+use crate::drawable::Align;
 use crate::drawable::NumOfLine;
 
-pub fn note<T: AsMut<[u8]>>(text: &str, align: Align, output: &mut [T]) -> NumOfLine {
+pub const MAX_NOTES: usize = 4; // per slot
+pub const MAX_NOTE_TEXT: usize = 64;
+const MAX_NOTE_LINES: usize = 2; // notes need more lines are scrolled as marquee
+const MARQUEE_INTERVAL: u32 = 250; // ms per character
+const MARQUEE_GAP: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Enum)]
+pub enum Slot {
+    Left,
+    Center,
+    Right,
+}
+
+impl Slot {
+    fn align(self) -> Align {
+        match self {
+            Self::Left => Align::Left,
+            Self::Center => Align::Center,
+            Self::Right => Align::Right,
+        }
+    }
+
+    fn width(self, line_width: usize) -> usize {
+        match self {
+            Self::Center => line_width,
+            _ => line_width / 2,
+        }
+    }
+}
+
+struct Note {
+    text: String<MAX_NOTE_TEXT>,
+    since: u32,
+    expire: Option<u32>,
+}
+
+/// Timed notes, timestamps are milliseconds from an arbitrary epoch
+#[derive(Default)]
+pub struct Notes {
+    slots: EnumMap<Slot, Vec<Note, MAX_NOTES>>,
+    now: u32,
+}
+
+impl Notes {
+    /// Post a note which expires after specified duration, or never if duration is None,
+    /// the oldest note of the slot will be dropped when slot is full
+    pub fn post(&mut self, slot: Slot, text: &str, duration: Option<u32>) {
+        let mut string: String<MAX_NOTE_TEXT> = String::new();
+        for ch in text.chars() {
+            if string.push(ch).is_err() {
+                break;
+            }
+        }
+        let notes = &mut self.slots[slot];
+        if notes.is_full() {
+            notes.remove(0);
+        }
+        let expire = duration.map(|duration| self.now.wrapping_add(duration));
+        notes
+            .push(Note {
+                text: string,
+                since: self.now,
+                expire,
+            })
+            .ok();
+    }
+
+    pub fn clear(&mut self, slot: Slot) {
+        self.slots[slot].clear();
+    }
+
+    /// Expire notes and advance marquee
+    pub fn update(&mut self, now: u32) {
+        self.now = now;
+        for (_, notes) in self.slots.iter_mut() {
+            notes.retain(|note| match note.expire {
+                Some(expire) => (expire.wrapping_sub(now) as i32) > 0,
+                None => true,
+            });
+        }
+    }
+
+    pub fn draw<T: AsMut<[u8]>>(&self, slot: Slot, output: &mut [T]) -> NumOfLine {
+        let mut index = 0;
+        for note in self.slots[slot].iter() {
+            if index >= output.len() {
+                break;
+            }
+            let line_width = output[index].as_mut().len();
+            let width = slot.width(line_width);
+            let text = note.text.as_str();
+            match wrap::<MAX_NOTE_LINES>(text, width) {
+                Some(lines) => {
+                    for line in lines.iter() {
+                        if index >= output.len() {
+                            break;
+                        }
+                        index += self::note(line, slot.align(), &mut output[index..]);
+                    }
+                }
+                None => {
+                    let elapsed = self.now.wrapping_sub(note.since);
+                    let offset = (elapsed / MARQUEE_INTERVAL) as usize;
+                    let buffer = output[index].as_mut();
+                    let start = match slot {
+                        Slot::Left => 0,
+                        _ => line_width - width,
+                    };
+                    marquee(text.as_bytes(), offset, &mut buffer[start..start + width]);
+                    index += 1;
+                }
+            }
+        }
+        index
+    }
+}
+
+/// Word wrap text to lines no longer than width, None if more than N lines required
+fn wrap<const N: usize>(text: &str, width: usize) -> Option<Vec<&str, N>> {
+    let mut lines: Vec<&str, N> = Vec::new();
+    for paragraph in text.split('\n') {
+        let (mut start, mut end, mut offset) = (0, 0, 0);
+        for word in paragraph.split(' ') {
+            if word.len() > width {
+                return None;
+            }
+            if offset + word.len() - start > width {
+                lines.push(&paragraph[start..end]).ok()?;
+                start = offset;
+            }
+            end = offset + word.len();
+            offset = end + 1;
+        }
+        lines.push(&paragraph[start..end]).ok()?;
+    }
+    Some(lines)
+}
+
+fn marquee(text: &[u8], offset: usize, output: &mut [u8]) {
+    let period = text.len() + MARQUEE_GAP;
+    for (i, byte) in output.iter_mut().enumerate() {
+        let index = (offset + i) % period;
+        *byte = if index < text.len() {
+            text[index]
+        } else {
+            b' '
+        };
+    }
+}
+
+pub(crate) fn note<T: AsMut<[u8]>>(text: &str, align: Align, output: &mut [T]) -> NumOfLine {
+    let mut canvas = Canvas::new(output);
+    let width = canvas.width();
     let mut index = 0;
     for line in text.split('\n') {
-        let buffer = output[index].as_mut();
-        let offset = match align {
-            Align::Center => buffer.len() / 2 - line.len() / 2,
-            Align::Right => buffer.len() - line.len(),
-            _ => 0,
-        };
-        buffer[offset..offset + line.len()].copy_from_slice(line.as_bytes());
+        if index >= canvas.height() {
+            break;
+        }
+        let column = columns(line.len(), align, width).start;
+        canvas.text(index, column, line, Align::Left);
         index += 1;
     }
     index
 }
+
+/// Columns occupied by a line of text aligned within width, clipped
+pub(crate) fn columns(len: usize, align: Align, width: usize) -> Range<usize> {
+    let len = len.min(width);
+    let offset = match align {
+        Align::Center => width / 2 - len / 2,
+        Align::Right => width - len,
+        _ => 0,
+    };
+    offset..offset + len
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+
+    use super::{wrap, Notes, Slot};
+
+    #[test]
+    fn test_wrap() {
+        let lines = wrap::<2>("LOW BATTERY LAND NOW", 12).unwrap();
+        assert_eq!(&["LOW BATTERY", "LAND NOW"], lines.as_slice());
+        let lines = wrap::<2>("LOW\nBAT", 12).unwrap();
+        assert_eq!(&["LOW", "BAT"], lines.as_slice());
+        assert!(wrap::<2>("LOW BATTERY LAND NOW", 8).is_none());
+        assert!(wrap::<2>("LOWBATTERYLANDNOW", 12).is_none());
+    }
+
+    #[test]
+    fn test_notes() {
+        let mut buffer = [[0u8; 12]; 2];
+        let mut notes = Notes::default();
+        notes.update(1000);
+        notes.post(Slot::Left, "MAN", Some(2000));
+        notes.post(Slot::Right, "REC", None);
+        assert_eq!(1, notes.draw(Slot::Left, &mut buffer));
+        assert_eq!(1, notes.draw(Slot::Right, &mut buffer));
+        assert_eq!("MAN      REC            ", to_utf8_string(&buffer));
+
+        notes.update(3000);
+        assert_eq!(0, notes.draw(Slot::Left, &mut buffer));
+        assert_eq!(1, notes.draw(Slot::Right, &mut buffer));
+
+        buffer.iter_mut().for_each(|b| b.zero());
+        notes.post(Slot::Center, "LOW BATTERY LAND NOW", None);
+        assert_eq!(2, notes.draw(Slot::Center, &mut buffer));
+        assert_eq!(" LOW BATTERY  LAND NOW  ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_marquee() {
+        let mut buffer = [[0u8; 12]; 1];
+        let mut notes = Notes::default();
+        notes.post(Slot::Center, "TELEMETRY_LOST", None);
+        notes.draw(Slot::Center, &mut buffer);
+        assert_eq!("TELEMETRY_LO", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        notes.update(500);
+        notes.draw(Slot::Center, &mut buffer);
+        assert_eq!("LEMETRY_LOST", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        notes.update(1000);
+        notes.draw(Slot::Center, &mut buffer);
+        assert_eq!("METRY_LOST  ", to_utf8_string(&buffer));
+    }
+}
@@ -1,6 +1,8 @@
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
 use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::telemetry::{Quantity, Telemetry};
 use crate::{AspectRatio, PixelRatio};
 #[allow(unused_imports)] // false warning
 use micromath::F32Ext;
@@ -67,9 +69,17 @@ impl<T: AsMut<[u8]>> Drawable<T> for Pitchladder {
         Align::Center
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let height = output.len() as isize;
-        let width = output[0].as_mut().len() as isize;
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Attitude]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        let height = canvas.height() as isize;
+        let width = canvas.width() as isize;
+        if width == 0 || height == 0 {
+            return 0;
+        }
 
         let roll = match telemetry.attitude.roll {
             -180..=-91 => 180 + telemetry.attitude.roll,
@@ -89,7 +99,7 @@ impl<T: AsMut<[u8]>> Drawable<T> for Pitchladder {
                 let y_index = y / symbols.len() as isize;
                 if 0 <= y_index && y_index < height && 0 <= x && x < width {
                     let symbol = symbols[y as usize % symbols.len()];
-                    output[y_index as usize].as_mut()[x as usize] = symbol;
+                    canvas.symbol(y_index as usize, x as usize, symbol);
                 }
             };
             let num_symbols = symbols.len() as isize;
@@ -108,7 +118,7 @@ impl<T: AsMut<[u8]>> Drawable<T> for Pitchladder {
                 let x_index = x / num_symbols as isize;
                 if 0 <= x_index && x_index < width {
                     let symbol = symbols[x as usize % symbols.len()];
-                    output[y as usize].as_mut()[x_index as usize] = symbol;
+                    canvas.symbol(y as usize, x_index as usize, symbol);
                 }
             }
         }
@@ -118,6 +128,7 @@ impl<T: AsMut<[u8]>> Drawable<T> for Pitchladder {
 
 #[cfg(test)]
 mod test {
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
@@ -134,7 +145,7 @@ mod test {
         let mut buffer = [[0u8; 32]; 9];
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 150, PX_RATIO, ASPECT_RATIO);
         let telemetry = Telemetry::default();
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -154,7 +165,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 150, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.pitch = 7;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -174,7 +185,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = -15;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -194,7 +205,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = 15;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -214,7 +225,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = -30;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                             ▁⎼\
                         .                         ▁⎼─⎺▔.\
@@ -234,7 +245,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = 45;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".   ⎼▔⎺⎼▁                      .\
                         .       ▔─▁                    .\
@@ -254,7 +265,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = -80;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".               ⎪              .\
                         .               ⎪              .\
@@ -275,7 +286,7 @@ mod test {
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = 71;
         telemetry.attitude.pitch = 10;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                ▏             .\
                         .                ▏             .\
@@ -295,7 +306,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         let mut telemetry = Telemetry::default();
         telemetry.attitude.roll = 90;
-        pitch_ladder.draw(&telemetry, &mut buffer);
+        pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".               |              .\
                         .               |              .\
@@ -316,7 +327,7 @@ mod test {
         let pitch_ladder = Pitchladder::new(&default_symbol_table(), 18, PX_RATIO, ASPECT_RATIO);
         for i in 0..180 {
             telemetry.attitude.roll = i as i16;
-            pitch_ladder.draw(&telemetry, &mut buffer);
+            pitch_ladder.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         }
     }
 }
@@ -1,8 +1,9 @@
-use heapless::String;
-
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::format::Format;
 use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::telemetry::{Quantity, Telemetry};
 
 pub struct RSSI {
     antenna: SymbolIndex,
@@ -21,20 +22,25 @@ impl<T: AsMut<[u8]>> Drawable<T> for RSSI {
         Align::TopLeft
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        buffer[0] = self.antenna;
-        buffer[1..3].iter_mut().for_each(|b| *b = b' ');
-        let string: String<3> = telemetry.rssi.into();
-        let bytes = string.as_bytes();
-        let offset = 4 - bytes.len();
-        buffer[offset..offset + bytes.len()].copy_from_slice(bytes);
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::RSSI]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        canvas.symbol(0, 0, self.antenna);
+        let format = Format {
+            width: 3,
+            ..Default::default()
+        };
+        canvas.number(0, 1, telemetry.rssi, &format, Align::Left);
         1
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
@@ -48,10 +54,10 @@ mod test {
         let rssi = RSSI::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
         telemetry.rssi = 100;
-        rssi.draw(&telemetry, &mut buffer);
+        rssi.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("⏉100", to_utf8_string(&buffer));
         telemetry.rssi = 90;
-        rssi.draw(&telemetry, &mut buffer);
+        rssi.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("⏉ 90", to_utf8_string(&buffer));
     }
 }
@@ -0,0 +1,90 @@
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::{Quantity, Telemetry};
+
+const MAX_OFFSET: isize = 4; // e.g. "    [●]    "
+const DECI_DEGREE_PER_CELL: isize = 20;
+
+pub struct Sideslip {
+    ball: SymbolIndex,
+}
+
+impl Sideslip {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            ball: symbols[Symbol::Ball],
+        }
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Sideslip {
+    fn align(&self) -> Align {
+        Align::Top
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Sideslip]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        let center = canvas.width() / 2;
+        if let Some(left) = center.checked_sub(1) {
+            canvas.symbol(0, left, b'[');
+        }
+        canvas.symbol(0, center + 1, b']');
+        let offset = telemetry.sideslip.0 as isize / DECI_DEGREE_PER_CELL;
+        let offset = offset.clamp(-MAX_OFFSET, MAX_OFFSET);
+        if let Some(column) = center.checked_add_signed(offset) {
+            canvas.symbol(0, column, self.ball);
+        }
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::symbol::default_symbol_table;
+    use crate::telemetry::Telemetry;
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+
+    use super::Sideslip;
+
+    #[test]
+    fn test_sideslip() {
+        let mut buffer = [[0u8; 11]];
+        let sideslip = Sideslip::new(&default_symbol_table());
+        let mut telemetry = Telemetry::default();
+        sideslip.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("    [●]    ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.sideslip = fixed!(1.9);
+        sideslip.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("    [●]    ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.sideslip = fixed!(-4.0);
+        sideslip.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("   ●[ ]    ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.sideslip = fixed!(12.7);
+        sideslip.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("    [ ]  ● ", to_utf8_string(&buffer));
+
+        let mut buffer = [[0u8; 1]];
+        telemetry.sideslip = fixed!(-12.7);
+        sideslip.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!(" ", to_utf8_string(&buffer));
+        telemetry.sideslip = fixed!(0.0);
+        sideslip.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("●", to_utf8_string(&buffer));
+    }
+}
@@ -1,45 +1,86 @@
-use heapless::String;
-
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::telemetry::Telemetry;
+use crate::format::Format;
+use crate::history::{arrows, Arrows, Trend};
+use crate::symbol::SymbolTable;
+use crate::telemetry::{Quantity, Telemetry};
+use crate::unit::SpeedUnit;
+
+pub struct Speed {
+    align: Align, // only accept TopLeft or Left
+    unit: SpeedUnit,
+    arrows: Arrows,
+    trend: Trend,
+}
+
+impl Speed {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            align: Align::Left,
+            unit: SpeedUnit::default(),
+            arrows: arrows(symbols),
+            trend: Trend::default(),
+        }
+    }
 
-pub struct Speed(Align); // only accept TopLeft or Left
+    pub fn set_unit(&mut self, unit: SpeedUnit) {
+        self.unit = unit;
+    }
 
-impl Default for Speed {
-    fn default() -> Self {
-        Self(Align::Left)
+    /// Arrow drawn between the number and unit unless steady
+    pub fn set_trend(&mut self, trend: Trend) {
+        self.trend = trend;
     }
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for Speed {
     fn align(&self) -> Align {
-        self.0
+        self.align
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Speed]
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        let string: String<5> = telemetry.speed().into();
-        let bytes = string.as_bytes();
-        let offset = 5 - bytes.len();
-        buffer[offset..offset + bytes.len()].copy_from_slice(bytes);
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let speed = self.unit.convert(telemetry.speed());
+        let mut canvas = Canvas::new(output);
+        canvas.number(0, 5, speed, &Format::default(), Align::Right);
+        if let Some(arrow) = self.trend.arrow(&self.arrows) {
+            canvas.symbol(0, 5, arrow);
+        }
+        canvas.text(0, 6, self.unit.label(), Align::Left);
         1
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
     use super::Speed;
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
+    use crate::history::Trend;
+    use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
-    use crate::test_utils::to_utf8_string;
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+    use crate::unit::SpeedUnit;
 
     #[test]
-    fn test_altitude() {
-        let mut buffer = [[0u8; 6]];
-        let altitude = Speed::default();
+    fn test_speed() {
+        let mut buffer = [[0u8; 9]];
+        let mut speed = Speed::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
-        telemetry.speed_vector.rho = 100;
-        altitude.draw(&telemetry, &mut buffer);
-        assert_eq!("  100 ", to_utf8_string(&buffer));
+        telemetry.speed_vector.rho = fixed!(51.4);
+        speed.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  100 KT ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        speed.set_unit(SpeedUnit::KilometerPerHour);
+        speed.set_trend(Trend::Falling);
+        speed.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  185↓KMH", to_utf8_string(&buffer));
     }
 }
@@ -1,15 +1,17 @@
-use core::cell::Cell;
+use core::sync::atomic::{AtomicI16, Ordering};
 
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
 use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::telemetry::{Quantity, Telemetry};
 use crate::AspectRatio;
 
 pub struct SpeedVector {
     vector: SymbolIndex,
     fov_width: u8,
     fov_height: u8,
-    counter: Cell<u8>,
+    last: [AtomicI16; 2], // cell offset from center of last drawn, row then column
 }
 
 impl SpeedVector {
@@ -18,16 +20,39 @@ impl SpeedVector {
             vector: symbols[Symbol::VeclocityVector],
             fov_width: aspect_ratio.diagonal_to_width(fov.into()) as u8,
             fov_height: aspect_ratio.diagonal_to_height(fov.into()) as u8,
-            counter: Cell::new(0),
+            last: [AtomicI16::new(NO_CELL), AtomicI16::new(NO_CELL)],
         }
     }
 }
 
+const MIN_SPEED: isize = 25; // in 0.1 m/s, vector shrinks toward center below
+const CELL_HYSTERESIS: isize = 1; // in 1/4 cell, beyond last cell before moving off
+const NO_CELL: i16 = i16::MIN;
+
+/// Cell offset of a position in 1/4 cell, the last one held within hysteresis
+fn to_cell(position: isize, last: &AtomicI16) -> isize {
+    let mut cell = position / 4;
+    let previous = last.load(Ordering::Relaxed);
+    if previous != NO_CELL {
+        let previous = previous as isize;
+        let lower = (position - CELL_HYSTERESIS) / 4;
+        let upper = (position + CELL_HYSTERESIS) / 4;
+        if lower == previous || upper == previous {
+            cell = previous;
+        }
+    }
+    last.store(
+        cell.clamp(-i16::MAX as isize, i16::MAX as isize) as i16,
+        Ordering::Relaxed,
+    );
+    cell
+}
+
 fn with_ratio(speed: isize, degree: isize) -> isize {
-    if speed >= 5 {
-        return degree as isize;
+    if speed >= MIN_SPEED {
+        return degree;
     }
-    degree * speed * speed / 5 / 5
+    degree * speed * speed / MIN_SPEED / MIN_SPEED
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for SpeedVector {
@@ -35,38 +60,49 @@ impl<T: AsMut<[u8]>> Drawable<T> for SpeedVector {
         Align::Center
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let speed = telemetry.speed() as isize;
-        let height = output.len() as isize;
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Speed]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        _: &mut [T],
+    ) -> NumOfLine {
+        let speed = telemetry.speed().0 as isize;
+        let mut canvas = Canvas::new(output);
+        let height = canvas.height() as isize;
         let y_degree = -with_ratio(speed, telemetry.speed_vector.phi as isize);
-        let mut y = y_degree * height / self.fov_height as isize + height / 2;
-        if y < 0 {
-            y = 0;
-        } else if y >= height {
-            y = height - 1;
-        }
-        let buffer = output[y as usize].as_mut();
-        let width = buffer.len() as isize;
+        let y_cell = to_cell(
+            y_degree * height * 4 / self.fov_height as isize,
+            &self.last[0],
+        );
+        let y = y_cell + height / 2;
+        let y = y.clamp(0, (height - 1).max(0)) as usize;
+        let width = canvas.width() as isize;
 
         let azimuth = telemetry.speed_vector.theta as isize;
         let x_degree = with_ratio(speed, azimuth);
-        let mut x = x_degree * width / self.fov_width as isize + width / 2;
-        if x < 0 {
-            x = 0;
-        } else if x >= width {
-            x = width - 1;
+        let x_cell = to_cell(
+            x_degree * width * 4 / self.fov_width as isize,
+            &self.last[1],
+        );
+        let x = x_cell + width / 2;
+        let x = x.clamp(0, (width - 1).max(0)) as usize;
+        if matches!(canvas.get(y, x), Some(0) | Some(b' ')) || clock.phase() {
+            canvas.symbol(y, x, self.vector);
         }
-        let byte = buffer[x as usize];
-        if byte == 0 || byte == b' ' || self.counter.get() % 2 == 0 {
-            buffer[x as usize] = self.vector;
-        }
-        self.counter.set(self.counter.get() + 1);
         0
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
@@ -82,7 +118,7 @@ mod test {
         let mut telemetry = Telemetry::default();
         telemetry.speed_vector.theta = 1;
         telemetry.speed_vector.phi = -1;
-        speed_vector.draw(&telemetry, &mut buffer);
+        speed_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -96,8 +132,9 @@ mod test {
         assert_eq!(expected, to_utf8_string(&buffer));
 
         buffer.iter_mut().for_each(|b| b.zero());
-        telemetry.speed_vector.rho = 5;
-        speed_vector.draw(&telemetry, &mut buffer);
+        let speed_vector = SpeedVector::new(&default_symbol_table(), 18, aspect_ratio!(16:9));
+        telemetry.speed_vector.rho = fixed!(2.5);
+        speed_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -113,7 +150,7 @@ mod test {
         buffer.iter_mut().for_each(|b| b.zero());
         telemetry.speed_vector.theta = 45;
         telemetry.speed_vector.phi = -45;
-        speed_vector.draw(&telemetry, &mut buffer);
+        speed_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -126,4 +163,23 @@ mod test {
                         .                              ⏂";
         assert_eq!(expected, to_utf8_string(&buffer));
     }
+
+    #[test]
+    fn test_cell_hysteresis() {
+        let mut buffer = [[0u8; 32]; 9];
+        let speed_vector = SpeedVector::new(&default_symbol_table(), 150, aspect_ratio!(16:9));
+        let mut telemetry = Telemetry::default();
+        telemetry.speed_vector.rho = fixed!(10.0);
+        let mut column = |theta| {
+            buffer.iter_mut().for_each(|b| b.zero());
+            telemetry.speed_vector.theta = theta;
+            speed_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+            buffer[4].iter().position(|&ch| ch != 0)
+        };
+        assert_eq!(Some(16), column(3));
+        assert_eq!(Some(16), column(5)); // within a quarter cell beyond
+        assert_eq!(Some(17), column(6));
+        assert_eq!(Some(17), column(4));
+        assert_eq!(Some(16), column(3));
+    }
 }
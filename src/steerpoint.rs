@@ -2,20 +2,42 @@ use core::fmt::Write;
 
 use heapless::String;
 
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::symbol::{to_number_with_dot, Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::format::{Decimal, Format};
+use crate::symbol::{Symbol, SymbolTable};
+use crate::telemetry::{Quantity, Telemetry};
+use crate::unit::DistanceUnit;
 
 pub struct Steerpoint {
-    zero_dot: SymbolIndex,
+    distance: Format,
+    reserve: u8,
+    unit: DistanceUnit,
 }
 
 impl Steerpoint {
     pub fn new(symbols: &SymbolTable) -> Self {
         Self {
-            zero_dot: symbols[Symbol::ZeroWithTraillingDot],
+            distance: Format {
+                decimals: 1,
+                zero_dot: Some(symbols[Symbol::ZeroWithTraillingDot]),
+                ..Default::default()
+            },
+            reserve: 20,
+            unit: DistanceUnit::default(),
         }
     }
+
+    pub fn set_unit(&mut self, unit: DistanceUnit) {
+        self.unit = unit;
+    }
+
+    /// Reserve percentage on top of battery to home before flashing RTH above steerpoint
+    pub fn set_reserve(&mut self, percentage: u8) {
+        self.reserve = percentage;
+    }
 }
 
 impl<T: AsMut<[u8]>> Drawable<T> for Steerpoint {
@@ -23,86 +45,148 @@ impl<T: AsMut<[u8]>> Drawable<T> for Steerpoint {
         Align::BottomRight
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let last_index = output.len() - 1;
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Steerpoint]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        if canvas.height() < 3 {
+            return 0; // too few rows
+        }
+        let (width, last_index) = (canvas.width(), canvas.height() - 1);
         let steerpoint = &telemetry.steerpoint;
 
         // TTG
-        let buffer = output[last_index].as_mut();
-        let buffer_len = buffer.len();
         let time_to_go = telemetry.time_to_go();
         let hours = (time_to_go / 3600) as u8;
         let minutes = (time_to_go / 60 % 60) as u8;
         let seconds = (time_to_go % 60) as u8;
         let mut string: String<8> = String::new();
         write!(string, "{:02}:{:02}:{:02}", hours, minutes, seconds).ok();
-        buffer[buffer_len - 8..].copy_from_slice(string.as_bytes());
+        canvas.text(last_index, width, string.as_str(), Align::Right);
 
         // distance
-        let buffer = output[last_index - 1].as_mut();
-        let rho = steerpoint.coordinate.rho;
-        let mut string: String<8> = String::new();
-        if steerpoint.coordinate.rho < 100 {
-            write!(string, "{}{}", rho, telemetry.unit.distance()).ok();
-            let bytes = string.as_bytes();
-            buffer[buffer_len - bytes.len()..].copy_from_slice(bytes);
-            buffer[buffer_len - 4] = to_number_with_dot(buffer[buffer_len - 4], self.zero_dot);
+        let distance = self.unit.convert(steerpoint.coordinate.rho);
+        let unit = self.unit.label();
+        canvas.text(last_index - 1, width, unit, Align::Right);
+        let column = width.saturating_sub(unit.len());
+        let distance = distance.rescale(1);
+        if distance.raw < 100 {
+            canvas.number(
+                last_index - 1,
+                column,
+                distance,
+                &self.distance,
+                Align::Right,
+            );
         } else {
-            write!(string, "{}{}", rho / 10, telemetry.unit.distance()).ok();
-            let bytes = string.as_bytes();
-            buffer[buffer_len - bytes.len()..].copy_from_slice(bytes);
+            let distance = Decimal {
+                raw: distance.raw / 10, // truncated
+                decimals: 0,
+            };
+            canvas.number(
+                last_index - 1,
+                column,
+                distance,
+                &Format::default(),
+                Align::Right,
+            );
         }
 
         // number and name
-        let buffer = output[last_index - 2].as_mut();
         let mut string: String<8> = String::new();
         write!(string, "{}/{:4}", steerpoint.number, steerpoint.name).ok();
-        let bytes = string.as_bytes();
-        buffer[buffer_len - bytes.len()..].copy_from_slice(bytes);
-        3
+        canvas.text(last_index - 2, width, string.as_str(), Align::Right);
+
+        if !telemetry.is_bingo(self.reserve) || last_index < 3 {
+            return 3;
+        }
+        if clock.is_on() {
+            canvas.text(last_index - 3, width, "RTH", Align::Right);
+        }
+        let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+        mark(
+            attributes,
+            last_index - 3,
+            width.saturating_sub(3)..width,
+            attribute,
+        );
+        4
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
     use crate::test_utils::{to_utf8_string, ZeroSlice};
+    use crate::unit::DistanceUnit;
 
     use super::Steerpoint;
 
     #[test]
     fn test_steerpoint() {
         let mut buffer = [[0u8; 10]; 3];
-        let steerpoint = Steerpoint::new(&default_symbol_table());
+        let mut steerpoint = Steerpoint::new(&default_symbol_table());
         let mut telemetry = Telemetry::default();
-        steerpoint.draw(&telemetry, &mut buffer);
+        steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("    0/HOME      ₀0NM  00:00:00", to_utf8_string(&buffer));
 
         buffer.iter_mut().for_each(|b| b.zero());
-        telemetry.steerpoint.coordinate.rho = 600;
-        steerpoint.draw(&telemetry, &mut buffer);
+        telemetry.steerpoint.coordinate.rho = 111_120;
+        steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         assert_eq!("    0/HOME      60NM  00:00:00", to_utf8_string(&buffer));
 
         buffer.iter_mut().for_each(|b| b.zero());
-        telemetry.speed_vector.rho = 60;
-        steerpoint.draw(&telemetry, &mut buffer);
-        assert_eq!("    0/HOME      60NM  01:00:00", to_utf8_string(&buffer));
+        telemetry.ground_speed = fixed!(30.9);
+        steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("    0/HOME      60NM  00:59:56", to_utf8_string(&buffer));
 
         buffer.iter_mut().for_each(|b| b.zero());
-        telemetry.speed_vector.rho = 61;
-        steerpoint.draw(&telemetry, &mut buffer);
-        assert_eq!("    0/HOME      60NM  00:59:00", to_utf8_string(&buffer));
+        steerpoint.set_unit(DistanceUnit::Kilometer);
+        steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("    0/HOME     111KM  00:59:56", to_utf8_string(&buffer));
 
         buffer.iter_mut().for_each(|b| b.zero());
-        telemetry.steerpoint.coordinate.rho = 99;
-        steerpoint.draw(&telemetry, &mut buffer);
-        assert_eq!("    0/HOME      ⒐9NM  00:09:44", to_utf8_string(&buffer));
+        telemetry.steerpoint.coordinate.rho = 9_900;
+        telemetry.ground_speed = fixed!(20.0);
+        steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("    0/HOME      ⒐9KM  00:08:15", to_utf8_string(&buffer));
+    }
 
-        buffer.iter_mut().for_each(|b| b.zero());
-        telemetry.steerpoint.coordinate.rho = 98;
-        steerpoint.draw(&telemetry, &mut buffer);
-        assert_eq!("    0/HOME      ⒐8NM  00:09:38", to_utf8_string(&buffer));
+    #[test]
+    fn test_bingo() {
+        let mut buffer = [[0u8; 10]; 4];
+        let steerpoint = Steerpoint::new(&default_symbol_table());
+        let mut telemetry = Telemetry {
+            home_distance: 12_000,
+            ..Default::default()
+        };
+        telemetry.ground_speed = fixed!(20.0);
+        telemetry.battery.remaining = 75;
+        telemetry.battery.current = fixed!(10.0);
+        telemetry.battery.consumed = 500;
+        assert_eq!(
+            4,
+            steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("       RTH", to_utf8_string(&buffer[..1]));
+
+        telemetry.home_distance = 1_200;
+        assert_eq!(
+            3,
+            steerpoint.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
     }
 }
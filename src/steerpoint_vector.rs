@@ -1,15 +1,14 @@
-use core::cell::Cell;
-
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
 use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
-use crate::telemetry::Telemetry;
+use crate::telemetry::{Quantity, Telemetry};
 use crate::AspectRatio;
 
 pub struct SteerpointVector {
     vector: SymbolIndex,
     fov_width: u8,
     fov_height: u8,
-    counter: Cell<u8>,
 }
 
 impl SteerpointVector {
@@ -18,7 +17,6 @@ impl SteerpointVector {
             vector: symbols[Symbol::Square],
             fov_width: aspect_ratio.diagonal_to_width(fov.into()) as u8,
             fov_height: aspect_ratio.diagonal_to_height(fov.into()) as u8,
-            counter: Cell::new(0),
         }
     }
 }
@@ -28,37 +26,38 @@ impl<T: AsMut<[u8]>> Drawable<T> for SteerpointVector {
         Align::Center
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Steerpoint]
+    }
+
+    fn draw(
+        &self,
+        telemetry: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        _: &mut [T],
+    ) -> NumOfLine {
         let steerpoint = &telemetry.steerpoint.coordinate;
         let phi = -steerpoint.phi as isize;
-        let height = output.len() as isize;
-        let mut y = phi * height / self.fov_height as isize + height / 2;
-        if y < 0 {
-            y = 0;
-        } else if y >= height {
-            y = height - 1;
-        }
-        let buffer = output[y as usize].as_mut();
-        let width = buffer.len() as isize;
+        let mut canvas = Canvas::new(output);
+        let height = canvas.height() as isize;
+        let y = phi * height / self.fov_height as isize + height / 2;
+        let y = y.clamp(0, (height - 1).max(0)) as usize;
+        let width = canvas.width() as isize;
 
         let azimuth = steerpoint.theta as isize;
-        let mut x = azimuth * width / self.fov_width as isize + width / 2;
-        if x < 0 {
-            x = 0;
-        } else if x >= width {
-            x = width - 1;
-        }
-        let byte = buffer[x as usize];
-        if byte == 0 || byte == b' ' || self.counter.get() % 2 == 1 {
-            buffer[x as usize] = self.vector;
+        let x = azimuth * width / self.fov_width as isize + width / 2;
+        let x = x.clamp(0, (width - 1).max(0)) as usize;
+        if matches!(canvas.get(y, x), Some(0) | Some(b' ')) || !clock.phase() {
+            canvas.symbol(y, x, self.vector);
         }
-        self.counter.set(self.counter.get() + 1);
         0
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::symbol::default_symbol_table;
     use crate::telemetry::Telemetry;
@@ -75,7 +74,7 @@ mod test {
         let mut telemetry = Telemetry::default();
         telemetry.steerpoint.coordinate.theta = 1;
         telemetry.steerpoint.coordinate.phi = -1;
-        steerpoint_vector.draw(&telemetry, &mut buffer);
+        steerpoint_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -90,7 +89,7 @@ mod test {
 
         buffer.iter_mut().for_each(|b| b.zero());
         telemetry.steerpoint.coordinate.phi = 1;
-        steerpoint_vector.draw(&telemetry, &mut buffer);
+        steerpoint_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
@@ -106,7 +105,7 @@ mod test {
         buffer.iter_mut().for_each(|b| b.zero());
         telemetry.steerpoint.coordinate.theta = 45;
         telemetry.steerpoint.coordinate.phi = -45;
-        steerpoint_vector.draw(&telemetry, &mut buffer);
+        steerpoint_vector.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
         fill_edge(&mut buffer);
         let expected = ".                              .\
                         .                              .\
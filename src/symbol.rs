@@ -23,6 +23,24 @@ pub enum Symbol {
     LineRight1,
     LineRight,
     ZeroWithTraillingDot,
+    Ball,
+    ChevronUp,
+    ChevronDown,
+    Circle,
+    BatteryEmpty,
+    BatteryQuarter,
+    BatteryHalf,
+    BatteryThreeQuarters,
+    Satellite,
+    Clock,
+    ArrowUp,
+    ArrowDown,
+    ArrowUpRight,
+    ArrowRight,
+    ArrowDownRight,
+    ArrowDownLeft,
+    ArrowLeft,
+    ArrowUpLeft,
 }
 
 pub fn to_number_with_dot(byte: u8, zero_with_trailling_dot: SymbolIndex) -> u8 {
@@ -60,5 +78,23 @@ pub fn default_symbol_table() -> SymbolTable {
         Symbol::LineRight1 => 138,
         Symbol::LineRight => 139, // ▕
         Symbol::ZeroWithTraillingDot => 144,
+        Symbol::Ball => 7,
+        Symbol::ChevronUp => 22, // ∧ or ASCII ^
+        Symbol::ChevronDown => 23, // ∨ or ASCII v
+        Symbol::Circle => 24, // ○ or ASCII o
+        Symbol::BatteryEmpty => 8,
+        Symbol::BatteryQuarter => 9,
+        Symbol::BatteryHalf => 10,
+        Symbol::BatteryThreeQuarters => 11,
+        Symbol::Satellite => 12,
+        Symbol::Clock => 13,
+        Symbol::ArrowUp => 14,
+        Symbol::ArrowDown => 15,
+        Symbol::ArrowUpRight => 16,
+        Symbol::ArrowRight => 17,
+        Symbol::ArrowDownRight => 18,
+        Symbol::ArrowDownLeft => 19,
+        Symbol::ArrowLeft => 20,
+        Symbol::ArrowUpLeft => 21,
     }
 }
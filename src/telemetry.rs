@@ -1,3 +1,4 @@
+use enum_map::{Enum, EnumMap};
 use fixed_point::{fixed, FixedPoint};
 
 #[derive(Copy, Clone, Debug)]
@@ -12,51 +13,28 @@ impl Default for Attitude {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct SphericalCoordinate {
-    pub rho: u16,   // ρ or radius
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SphericalCoordinate<R> {
+    pub rho: R,     // ρ or radius
     pub theta: i16, // θ, -180 <= θ <= 180, azimuthal angle
     pub phi: i8,    // φ, -90 <= φ <= 90, polar angle, negative means desend
 }
 
-impl Default for SphericalCoordinate {
-    fn default() -> Self {
-        Self {
-            rho: 0,
-            theta: 0,
-            phi: 0,
-        }
-    }
-}
+/// Speed in meters per second
+pub type Speed = FixedPoint<u16, 1>;
 
-#[derive(Copy, Clone, Debug)]
-pub enum Unit {
-    Aviation,
-    Metric,
-}
-
-impl Unit {
-    pub fn distance(self) -> &'static str {
-        match self {
-            Self::Aviation => "NM",
-            Self::Metric => "KM",
-        }
-    }
-
-    pub fn elevation(self) -> &'static str {
-        match self {
-            Self::Aviation => "FT",
-            Self::Metric => "M",
-        }
-    }
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Wind {
+    pub direction: u16, // [0, 360), where wind blows from, ref to north
+    pub speed: Speed,   //
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct Steerpoint<'a> {
-    pub number: u8,                      // e.g. 0 means home or base
-    pub name: &'a str,                   // e.g. "HOME" when number = 0
-    pub heading: u16,                    //
-    pub coordinate: SphericalCoordinate, // rho unit km or nm * 10
+    pub number: u8,                           // e.g. 0 means home or base
+    pub name: &'a str,                        // e.g. "HOME" when number = 0
+    pub heading: u16,                         //
+    pub coordinate: SphericalCoordinate<u32>, // rho in meters
 }
 
 impl<'a> Default for Steerpoint<'a> {
@@ -70,61 +48,373 @@ impl<'a> Default for Steerpoint<'a> {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct Battery {
+    pub remaining: u8,               // percentage
+    pub voltage: FixedPoint<u16, 2>, // in volt
+    pub current: FixedPoint<u16, 1>, // in ampere
+    pub consumed: u16,               // in mAh
+    pub capacity: u16,               // in mAh, 0 means estimate from remaining percentage
+    pub cells: u8,                   // number of cells, 0 means auto detect
+}
+
+impl Default for Battery {
+    fn default() -> Self {
+        Self {
+            remaining: 100,
+            voltage: fixed!(0.0),
+            current: fixed!(0.0),
+            consumed: 0,
+            capacity: 0,
+            cells: 0,
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Enum)]
+pub enum FlightMode {
+    #[default]
+    Acro,
+    Angle,
+    Horizon,
+    RTH,
+    Loiter,
+    Auto,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Fix {
+    #[default]
+    None,
+    Fix2D,
+    Fix3D,
+}
+
 #[derive(Copy, Clone, Debug, Default)]
-pub struct Notes<'a> {
-    pub left: &'a str,
-    pub center: &'a str,
-    pub right: &'a str,
+pub struct Gnss {
+    pub satellites: u8,           //
+    pub fix: Fix,                 //
+    pub hdop: FixedPoint<u16, 1>, //
+    pub latitude: i32,            // in degree * 10^7, negative means south
+    pub longitude: i32,           // in degree * 10^7, negative means west
+}
+
+pub const MAX_MOTORS: usize = 8;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Esc {
+    pub rpm: u32,                    //
+    pub temperature: i16,            // in celsius
+    pub current: FixedPoint<u16, 1>, // in ampere
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Link<'a> {
+    pub quality: u8,        // link quality percentage
+    pub uplink_rssi: i16,   // in dBm
+    pub downlink_rssi: i16, // in dBm
+    pub snr: i8,            // in dB
+    pub rf_mode: &'a str,   // e.g. "500HZ"
+    pub tx_power: u16,      // in mW
+}
+
+/// Telemetry quantities which may be unavailable, e.g. sensor missing or failed
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, PartialEq, Enum)]
+pub enum Quantity {
+    Altitude,
+    AOA,
+    Attitude,
+    Heading,
+    Course,
+    Battery,
+    Esc,
+    GForce,
+    Gnss,
+    Height,
+    HomeDistance,
+    Link,
+    RSSI,
+    Sideslip,
+    Speed, // speed vector
+    Airspeed,
+    GroundSpeed,
+    Wind,
+    Vario,
+    Steerpoint,
+    Throttle,
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct Telemetry<'a> {
-    pub altitude: i16,              // feets or meters
-    pub aoa: FixedPoint<i8, 1>,     // in degree
-    pub attitude: Attitude,         // in degree
-    pub heading: u16,               // [0, 360), ref to north
-    pub battery: u8,                // percentage
+    pub altitude: FixedPoint<i32, 1>,             // in meters
+    pub aoa: FixedPoint<i8, 1>,                   // in degree
+    pub armed: bool,                              //
+    pub arming_blocker: &'a str, // reason arming is blocked, e.g. "THROTTLE", empty if none
+    pub attitude: Attitude,      // in degree
+    pub heading: u16,            // [0, 360), ref to north
+    pub course: u16,             // [0, 360), over ground ref to north
+    pub battery: Battery,        //
+    pub escs: [Esc; MAX_MOTORS], //
+    pub motors: u8,              // number of motors with ESC telemetry
+    pub flight_mode: FlightMode, //
     pub g_force: FixedPoint<i8, 1>, // in g
-    pub height: i16,                // feets or meters, same with altitude, i16::MIN means N/A
-    pub notes: Notes<'a>,           //
-    pub rssi: u8,                   // percentage
-    pub unit: Unit,
-    pub speed_vector: SphericalCoordinate, // rho unit km/h or knot, theta ref to attitude
-    pub vario: i16,                        // feets/min or m/s
-    pub steerpoint: Steerpoint<'a>,        //
+    pub gnss: Gnss,              //
+    pub height: FixedPoint<i32, 1>, // in meters
+    pub home_distance: u32,      // in meters
+    pub link: Link<'a>,          //
+    pub rssi: u8,                // percentage
+    pub sideslip: FixedPoint<i8, 1>, // in degree, positive means relative wind from right
+    pub speed_vector: SphericalCoordinate<Speed>, // theta ref to attitude
+    pub airspeed: Speed,         // true airspeed
+    pub ground_speed: Speed,     //
+    pub wind: Wind,              //
+    pub vario: FixedPoint<i16, 2>, // in meters per second
+    pub steerpoint: Steerpoint<'a>, //
+    pub throttle: u8,            // percentage
+    pub unavailable: EnumMap<Quantity, bool>, // true if sensor missing or value invalid
+    pub updated: EnumMap<Quantity, u32>, // in ms on HUD clock epoch, 0 if unstamped
+    pub derived: EnumMap<Quantity, bool>, // true if estimated rather than reported
 }
 
 impl<'a> Default for Telemetry<'a> {
     fn default() -> Telemetry<'a> {
         Telemetry {
-            altitude: 0,
+            altitude: fixed!(0.0),
+            armed: false,
+            arming_blocker: "",
             attitude: Attitude::default(),
             heading: 0,
+            course: 0,
             aoa: fixed!(0.0),
-            battery: 100,
+            battery: Battery::default(),
+            escs: [Esc::default(); MAX_MOTORS],
+            motors: 0,
+            flight_mode: FlightMode::default(),
             g_force: fixed!(1.0),
-            height: 0,
-            notes: Default::default(),
+            gnss: Gnss::default(),
+            height: fixed!(0.0),
+            home_distance: 0,
+            link: Link::default(),
             rssi: 0,
+            sideslip: fixed!(0.0),
             steerpoint: Steerpoint::default(),
-            unit: Unit::Aviation,
             speed_vector: SphericalCoordinate::default(),
-            vario: 0,
+            airspeed: fixed!(0.0),
+            ground_speed: fixed!(0.0),
+            wind: Wind::default(),
+            vario: fixed!(0.0),
+            throttle: 0,
+            unavailable: EnumMap::default(),
+            updated: EnumMap::default(),
+            derived: EnumMap::default(),
         }
     }
 }
 
 impl<'a> Telemetry<'a> {
-    pub fn speed(&self) -> u16 {
+    pub fn is_available(&self, quantity: Quantity) -> bool {
+        !self.unavailable[quantity]
+    }
+
+    pub fn set_available(&mut self, quantity: Quantity, available: bool) {
+        self.unavailable[quantity] = !available;
+    }
+
+    pub fn is_derived(&self, quantity: Quantity) -> bool {
+        self.derived[quantity]
+    }
+
+    /// Mark quantity available as estimated rather than reported
+    pub fn set_derived(&mut self, quantity: Quantity) {
+        self.unavailable[quantity] = false;
+        self.derived[quantity] = true;
+    }
+
+    /// Mark quantity updated at now in milliseconds
+    pub fn set_updated(&mut self, quantity: Quantity, now: u32) {
+        self.updated[quantity] = now;
+    }
+
+    pub fn is_stamped(&self, quantity: Quantity) -> bool {
+        self.updated[quantity] != 0
+    }
+
+    /// Not updated within timeout in milliseconds, never stale if timeout is 0 or unstamped
+    pub fn is_stale(&self, quantity: Quantity, now: u32, timeout: u32) -> bool {
+        let updated = self.updated[quantity];
+        timeout > 0 && updated != 0 && now.wrapping_sub(updated) >= timeout
+    }
+
+    pub fn escs(&self) -> &[Esc] {
+        &self.escs[..(self.motors as usize).min(MAX_MOTORS)]
+    }
+
+    /// In celsius, None if no ESC telemetry
+    pub fn max_esc_temperature(&self) -> Option<i16> {
+        self.escs().iter().map(|esc| esc.temperature).max()
+    }
+
+    pub fn speed(&self) -> Speed {
         self.speed_vector.rho
     }
 
+    /// In seconds over ground, saturated, None if away but not moving
+    fn time_to(&self, rho: u32) -> Option<u32> {
+        let speed = self.ground_speed.0 as u64; // in 0.1 m/s
+        if rho == 0 {
+            return Some(0);
+        }
+        if speed == 0 || !self.is_available(Quantity::GroundSpeed) {
+            return None;
+        }
+        Some((rho as u64 * 10 / speed).min(u32::MAX as u64) as u32)
+    }
+
+    /// In seconds, 0 if not moving
     pub fn time_to_go(&self) -> u32 {
-        let rho = self.steerpoint.coordinate.rho;
-        let speed = self.speed_vector.rho;
-        if rho > 0 && speed > 0 {
-            return rho as u32 * 3600 / 10 / speed as u32; // rho / 10 / (speed / 3600)
+        self.time_to(self.steerpoint.coordinate.rho).unwrap_or(0)
+    }
+
+    /// In seconds, saturated if away from home but not moving
+    pub fn time_to_home(&self) -> u32 {
+        self.time_to(self.home_distance).unwrap_or(u32::MAX)
+    }
+
+    /// In mAh, estimated from consumed and remaining percentage if capacity not specified
+    pub fn remaining_capacity(&self) -> Option<u32> {
+        let battery = &self.battery;
+        if battery.capacity > 0 {
+            return Some((battery.capacity as u32).saturating_sub(battery.consumed as u32));
+        }
+        let remaining = battery.remaining.min(100) as u32;
+        if remaining == 100 || battery.consumed == 0 {
+            return None;
+        }
+        Some(battery.consumed as u32 * remaining / (100 - remaining))
+    }
+
+    /// In seconds with current consumption rate
+    pub fn flight_time_remaining(&self) -> Option<u32> {
+        let current = self.battery.current.0 as u32 * 100; // mA
+        if current == 0 {
+            return None;
         }
-        0
+        let seconds = self.remaining_capacity()? as u64 * 3600 / current as u64;
+        Some(seconds.min(u32::MAX as u64) as u32)
+    }
+
+    /// In mAh with current consumption rate and speed, saturated
+    pub fn battery_to_home(&self) -> u32 {
+        let current = self.battery.current.0 as u64 * 100; // mA
+        let required = current * self.time_to_home() as u64 / 3600;
+        required.min(u32::MAX as u64) as u32
+    }
+
+    /// Whether remaining battery below required to reach home plus reserve percentage
+    pub fn is_bingo(&self, reserve: u8) -> bool {
+        let required = [Quantity::Battery, Quantity::HomeDistance];
+        if !required.iter().all(|&quantity| self.is_available(quantity)) {
+            return false;
+        }
+        let remaining = match self.remaining_capacity() {
+            Some(remaining) => remaining,
+            None => return false,
+        };
+        let total = remaining + self.battery.consumed as u32;
+        let reserve = total * reserve as u32 / 100;
+        remaining < self.battery_to_home().saturating_add(reserve)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::fixed;
+
+    use super::{Battery, Quantity, Telemetry};
+
+    #[test]
+    fn test_battery_estimate() {
+        let mut telemetry = Telemetry {
+            battery: Battery {
+                remaining: 75,
+                current: fixed!(10.0),
+                consumed: 500,
+                ..Default::default()
+            },
+            home_distance: 12_000,
+            ground_speed: fixed!(20.0),
+            ..Default::default()
+        };
+        assert_eq!(Some(1500), telemetry.remaining_capacity());
+        assert_eq!(Some(540), telemetry.flight_time_remaining());
+        assert_eq!(600, telemetry.time_to_home());
+        assert_eq!(1666, telemetry.battery_to_home());
+        assert!(telemetry.is_bingo(0));
+
+        telemetry.battery.capacity = 5000;
+        assert_eq!(Some(4500), telemetry.remaining_capacity());
+        assert!(!telemetry.is_bingo(20));
+        assert!(telemetry.is_bingo(60));
+        telemetry.set_available(Quantity::HomeDistance, false);
+        assert!(!telemetry.is_bingo(60));
+        telemetry.set_available(Quantity::HomeDistance, true);
+
+        telemetry.battery.current = fixed!(0.0);
+        assert_eq!(None, telemetry.flight_time_remaining());
+    }
+
+    #[test]
+    fn test_slow_far_from_home() {
+        let mut telemetry = Telemetry {
+            battery: Battery {
+                current: fixed!(120.0),
+                capacity: 5000,
+                ..Default::default()
+            },
+            home_distance: 10_000,
+            ground_speed: fixed!(0.2),
+            ..Default::default()
+        };
+        assert_eq!(50_000, telemetry.time_to_home());
+        assert_eq!(1_666_666, telemetry.battery_to_home());
+        assert!(telemetry.is_bingo(0));
+
+        telemetry.home_distance = u32::MAX;
+        telemetry.ground_speed = fixed!(0.1);
+        assert_eq!(u32::MAX, telemetry.time_to_home());
+        assert_eq!(u32::MAX, telemetry.battery_to_home());
+        assert!(telemetry.is_bingo(100));
+    }
+
+    #[test]
+    fn test_hovering_far_from_home() {
+        let telemetry = Telemetry {
+            battery: Battery {
+                current: fixed!(20.0),
+                capacity: 1200,
+                consumed: 1000,
+                ..Default::default()
+            },
+            home_distance: 5_000,
+            ..Default::default()
+        };
+        assert_eq!(u32::MAX, telemetry.time_to_home());
+        assert_eq!(u32::MAX, telemetry.battery_to_home());
+        assert!(telemetry.is_bingo(10));
+        assert_eq!(0, telemetry.time_to_go());
+    }
+
+    #[test]
+    fn test_stale() {
+        let mut telemetry = Telemetry::default();
+        telemetry.set_updated(Quantity::Altitude, 1000);
+        assert!(!telemetry.is_stale(Quantity::Altitude, 1499, 500));
+        assert!(telemetry.is_stale(Quantity::Altitude, 1500, 500));
+        assert!(!telemetry.is_stale(Quantity::Altitude, 1500, 0));
+        assert!(!telemetry.is_stale(Quantity::Vario, 1500, 500)); // unstamped
+        telemetry.set_updated(Quantity::Altitude, u32::MAX);
+        assert!(!telemetry.is_stale(Quantity::Altitude, 100, 500));
     }
 }
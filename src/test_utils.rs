@@ -3,7 +3,9 @@ use std::vec::Vec;
 
 use ascii::ToAsciiChar;
 
-const SYMBOLS: &str = "⏉β╵⏂⍺☐";
+use crate::clock::{Clock, DEFAULT_BLINK_PERIOD};
+
+const SYMBOLS: &str = "⏉β╵⏂⍺☐●▯▂▄▆⊕◷↑↓↗→↘↙←↖∧∨○";
 const LINES: &str = "▔⎺⎻─⎼⎽▁▏▏|⎪⎪";
 const DOTTED_NUMBERS: &str = "₀⒈⒉⒊⒋⒌⒍⒎⒏⒐";
 
@@ -16,7 +18,7 @@ pub fn to_utf8_string<T: AsRef<[u8]>>(screen: &[T]) -> String {
         for &byte in line.iter() {
             output.push(match byte {
                 0 => ' ',
-                1..=6 => *symbols.get(byte as usize - 1).unwrap(),
+                1..=24 => *symbols.get(byte as usize - 1).unwrap(),
                 128..=139 => *lines.get(byte as usize - 128).unwrap(),
                 144..=154 => *dotted_numbers.get(byte as usize - 144).unwrap(),
                 _ => byte.to_ascii_char().unwrap().as_char(),
@@ -49,3 +51,11 @@ impl ZeroSlice<u8> for [u8] {
         }
     }
 }
+
+/// Clock at the invisible phase of blinking
+pub fn blink_off() -> Clock {
+    Clock {
+        now: DEFAULT_BLINK_PERIOD / 2,
+        ..Default::default()
+    }
+}
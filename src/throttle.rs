@@ -0,0 +1,92 @@
+use core::fmt::Write;
+
+use heapless::String;
+
+use crate::bar_graph::BarGraph;
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::symbol::SymbolTable;
+use crate::telemetry::{Quantity, Telemetry};
+
+const BAR_WIDTH: usize = 10;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum ThrottleStyle {
+    #[default]
+    Readout, // e.g. THR 45
+    Bar, // e.g. [────|     ]
+}
+
+pub struct Throttle {
+    style: ThrottleStyle,
+    bar: BarGraph,
+}
+
+impl Throttle {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        let mut bar = BarGraph::new(symbols, |telemetry| telemetry.throttle as i32, 0, 100);
+        bar.set_length(BAR_WIDTH);
+        Self {
+            style: ThrottleStyle::default(),
+            bar,
+        }
+    }
+
+    pub fn set_style(&mut self, style: ThrottleStyle) {
+        self.style = style;
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Throttle {
+    fn align(&self) -> Align {
+        Align::Left
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Throttle]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        if self.style == ThrottleStyle::Bar {
+            canvas.symbol(0, 0, b'[');
+            self.bar
+                .draw_horizontal(telemetry.throttle as i32, &mut canvas, 0, 1);
+            canvas.symbol(0, BAR_WIDTH + 1, b']');
+            return 1;
+        }
+        let mut string: String<8> = String::new();
+        write!(string, "THR{:3}", telemetry.throttle.min(100)).ok();
+        canvas.text(0, 0, string.as_str(), Align::Left);
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::symbol::default_symbol_table;
+    use crate::telemetry::Telemetry;
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+
+    use super::{Throttle, ThrottleStyle};
+
+    #[test]
+    fn test_throttle() {
+        let mut buffer = [[0u8; 12]];
+        let mut throttle = Throttle::new(&default_symbol_table());
+        let telemetry = Telemetry {
+            throttle: 45,
+            ..Default::default()
+        };
+        throttle.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("THR 45      ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        throttle.set_style(ThrottleStyle::Bar);
+        throttle.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("[────|     ]", to_utf8_string(&buffer));
+    }
+}
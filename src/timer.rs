@@ -0,0 +1,276 @@
+use core::fmt::Write;
+
+use heapless::String;
+
+use crate::attribute::{mark, Attribute, Color};
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::Telemetry;
+
+pub const MAX_TIMERS: usize = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Source {
+    PowerOn,
+    Armed,
+    Countdown(u32), // in seconds, e.g. a racing heat or battery swap reminder
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimeFormat {
+    HourMinuteSecond, // HH:MM:SS
+    MinuteSecond,     // MM:SS, minutes saturates at 99
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Timer {
+    pub source: Source,
+    pub format: TimeFormat,
+    pub alarm: bool, // blink when countdown expired
+}
+
+struct State {
+    timer: Timer,
+    started: Option<u32>, // countdown only
+}
+
+/// Timestamps are milliseconds from an arbitrary epoch
+pub struct Timers {
+    clock: SymbolIndex,
+    timers: [Option<State>; MAX_TIMERS], // slot of a removed timer left empty
+    now: u32,
+    power_on: Option<u32>,
+    armed: u32,
+    armed_since: Option<u32>,
+}
+
+impl Timers {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            clock: symbols[Symbol::Clock],
+            timers: Default::default(),
+            now: 0,
+            power_on: None,
+            armed: 0,
+            armed_since: None,
+        }
+    }
+
+    /// Returns index of the timer, valid until removed, or None if full
+    pub fn add(&mut self, timer: Timer) -> Option<usize> {
+        let index = self.timers.iter().position(Option::is_none)?;
+        self.timers[index] = Some(State {
+            timer,
+            started: None,
+        });
+        Some(index)
+    }
+
+    /// Indexes of other timers unchanged
+    pub fn remove(&mut self, index: usize) {
+        if let Some(slot) = self.timers.get_mut(index) {
+            *slot = None;
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&State> {
+        self.timers.get(index)?.as_ref()
+    }
+
+    /// Start or restart countdown
+    pub fn start(&mut self, index: usize) {
+        if let Some(Some(state)) = self.timers.get_mut(index) {
+            state.started = Some(self.now);
+        }
+    }
+
+    /// Stop countdown and reset to its duration
+    pub fn reset(&mut self, index: usize) {
+        if let Some(Some(state)) = self.timers.get_mut(index) {
+            state.started = None;
+        }
+    }
+
+    /// Accumulate power on time since first update and armed time
+    pub fn update(&mut self, now: u32, armed: bool) {
+        if self.power_on.is_none() {
+            self.power_on = Some(now);
+        }
+        if let Some(since) = self.armed_since {
+            self.armed = self.armed.wrapping_add(now.wrapping_sub(since));
+        }
+        self.armed_since = if armed { Some(now) } else { None };
+        self.now = now;
+    }
+
+    /// In seconds, remaining time for countdown
+    pub fn seconds(&self, index: usize) -> Option<u32> {
+        let state = self.get(index)?;
+        let millis = match state.timer.source {
+            Source::PowerOn => self.now.wrapping_sub(self.power_on.unwrap_or(self.now)),
+            Source::Armed => self.armed,
+            Source::Countdown(duration) => {
+                let elapsed = match state.started {
+                    Some(started) => self.now.wrapping_sub(started),
+                    None => 0,
+                };
+                return Some(duration.saturating_sub(elapsed / 1000));
+            }
+        };
+        Some(millis / 1000)
+    }
+
+    pub fn is_expired(&self, index: usize) -> bool {
+        match self.get(index).map(|state| state.timer.source) {
+            Some(Source::Countdown(_)) => self.seconds(index) == Some(0),
+            _ => false,
+        }
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Timers {
+    fn align(&self) -> Align {
+        Align::TopLeft
+    }
+
+    fn draw(
+        &self,
+        _: &Telemetry,
+        clock: &Clock,
+        output: &mut [T],
+        attributes: &mut [T],
+    ) -> NumOfLine {
+        let timers = self.timers.iter().enumerate();
+        let timers = timers.filter_map(|(index, state)| Some((index, state.as_ref()?)));
+        for (row, (index, state)) in timers.take(output.len()).enumerate() {
+            let alarm = state.timer.alarm && self.is_expired(index);
+            if alarm {
+                let attribute = Attribute::BLINK | Attribute::color(Color::Red);
+                mark(attributes, row, 0..usize::MAX, attribute);
+                if !clock.is_on() {
+                    continue;
+                }
+            }
+            let seconds = self.seconds(index).unwrap_or(0);
+            let mut string: String<8> = String::new();
+            match state.timer.format {
+                TimeFormat::HourMinuteSecond => {
+                    let (hours, minutes) = ((seconds / 3600).min(99), seconds / 60 % 60);
+                    write!(string, "{:02}:{:02}:{:02}", hours, minutes, seconds % 60).ok();
+                }
+                TimeFormat::MinuteSecond => {
+                    let minutes = (seconds / 60).min(99);
+                    write!(string, "{:02}:{:02}", minutes, seconds % 60).ok();
+                }
+            }
+            let mut canvas = Canvas::new(&mut output[row..]);
+            canvas.symbol(0, 0, self.clock);
+            canvas.text(0, 1, string.as_str(), Align::Left);
+        }
+        self.timers.iter().flatten().count().min(output.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::symbol::default_symbol_table;
+    use crate::telemetry::Telemetry;
+    use crate::test_utils::{blink_off, to_utf8_string, ZeroSlice};
+
+    use super::{Source, TimeFormat, Timer, Timers};
+
+    #[test]
+    fn test_timers() {
+        let mut buffer = [[0u8; 9]; 2];
+        let mut timers = Timers::new(&default_symbol_table());
+        let format = TimeFormat::HourMinuteSecond;
+        timers.add(Timer {
+            source: Source::PowerOn,
+            format,
+            alarm: false,
+        });
+        let format = TimeFormat::MinuteSecond;
+        let armed = timers.add(Timer {
+            source: Source::Armed,
+            format,
+            alarm: false,
+        });
+        timers.update(1000, false);
+        timers.update(3000, true);
+        timers.update(63000, false);
+        timers.update(3663000, false);
+        assert_eq!(Some(60), timers.seconds(armed.unwrap()));
+
+        let telemetry = Telemetry::default();
+        assert_eq!(
+            2,
+            timers.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("◷01:01:02◷01:00   ", to_utf8_string(&buffer));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut buffer = [[0u8; 6]; 2];
+        let mut timers = Timers::new(&default_symbol_table());
+        let timer = |duration| Timer {
+            source: Source::Countdown(duration),
+            format: TimeFormat::MinuteSecond,
+            alarm: false,
+        };
+        let first = timers.add(timer(60)).unwrap();
+        let second = timers.add(timer(90)).unwrap();
+        timers.remove(first);
+        assert_eq!(None, timers.seconds(first));
+        assert_eq!(Some(90), timers.seconds(second));
+
+        let telemetry = Telemetry::default();
+        assert_eq!(
+            1,
+            timers.draw(&telemetry, &Clock::default(), &mut buffer, &mut [])
+        );
+        assert_eq!("◷01:30      ", to_utf8_string(&buffer));
+
+        assert_eq!(Some(first), timers.add(timer(30)));
+        assert_eq!(Some(90), timers.seconds(second));
+    }
+
+    #[test]
+    fn test_countdown() {
+        let mut buffer = [[0u8; 6]; 1];
+        let mut timers = Timers::new(&default_symbol_table());
+        let index = timers
+            .add(Timer {
+                source: Source::Countdown(90),
+                format: TimeFormat::MinuteSecond,
+                alarm: true,
+            })
+            .unwrap();
+        let telemetry = Telemetry::default();
+        timers.update(1000, false);
+        timers.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("◷01:30", to_utf8_string(&buffer));
+
+        timers.start(index);
+        timers.update(31000, false);
+        buffer[0].zero();
+        timers.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("◷01:00", to_utf8_string(&buffer));
+
+        timers.update(91000, false);
+        assert!(timers.is_expired(index));
+        buffer[0].zero();
+        timers.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("◷00:00", to_utf8_string(&buffer));
+        buffer[0].zero();
+        timers.draw(&telemetry, &blink_off(), &mut buffer, &mut []);
+        assert_eq!("      ", to_utf8_string(&buffer));
+
+        timers.reset(index);
+        assert!(!timers.is_expired(index));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::format::Decimal;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum SpeedUnit {
+    #[default]
+    Knot,
+    KilometerPerHour,
+    MilePerHour,
+    MeterPerSecond,
+}
+
+impl SpeedUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Knot => "KT",
+            Self::KilometerPerHour => "KMH",
+            Self::MilePerHour => "MPH",
+            Self::MeterPerSecond => "M/S",
+        }
+    }
+
+    /// From meters per second
+    pub fn convert<V: Into<Decimal>>(self, value: V) -> Decimal {
+        let value = value.into();
+        match self {
+            Self::Knot => scale(value, 1_000_000, 514_444),
+            Self::KilometerPerHour => scale(value, 36, 10),
+            Self::MilePerHour => scale(value, 1_000_000, 447_040),
+            Self::MeterPerSecond => value,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum AltitudeUnit {
+    #[default]
+    Feet,
+    Meter,
+}
+
+impl AltitudeUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Feet => "FT",
+            Self::Meter => "M",
+        }
+    }
+
+    /// From meters
+    pub fn convert<V: Into<Decimal>>(self, value: V) -> Decimal {
+        let value = value.into();
+        match self {
+            Self::Feet => scale(value, 10_000, 3_048),
+            Self::Meter => value,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum VerticalSpeedUnit {
+    #[default]
+    FeetPerMinute,
+    MeterPerSecond,
+}
+
+impl VerticalSpeedUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FeetPerMinute => "FPM",
+            Self::MeterPerSecond => "M/S",
+        }
+    }
+
+    /// Number of decimals worth displaying
+    pub fn decimals(self) -> u8 {
+        match self {
+            Self::FeetPerMinute => 0,
+            Self::MeterPerSecond => 1,
+        }
+    }
+
+    /// From meters per second
+    pub fn convert<V: Into<Decimal>>(self, value: V) -> Decimal {
+        let value = value.into();
+        match self {
+            Self::FeetPerMinute => scale(value, 600_000, 3_048),
+            Self::MeterPerSecond => value,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum DistanceUnit {
+    #[default]
+    NauticalMile,
+    Kilometer,
+    Mile,
+}
+
+impl DistanceUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::NauticalMile => "NM",
+            Self::Kilometer => "KM",
+            Self::Mile => "MI",
+        }
+    }
+
+    /// From meters, with 2 more decimals at least
+    pub fn convert<V: Into<Decimal>>(self, value: V) -> Decimal {
+        let value = value.into();
+        let value = value.rescale(value.decimals + 2);
+        match self {
+            Self::NauticalMile => scale(value, 1, 1_852),
+            Self::Kilometer => scale(value, 1, 1_000),
+            Self::Mile => scale(value, 1_000, 1_609_344),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Celsius => "C",
+            Self::Fahrenheit => "F",
+        }
+    }
+
+    /// From celsius
+    pub fn convert<V: Into<Decimal>>(self, value: V) -> Decimal {
+        let value = value.into();
+        match self {
+            Self::Celsius => value,
+            Self::Fahrenheit => {
+                let mut value = scale(value, 9, 5);
+                value.raw += 32 * 10i64.pow(value.decimals as u32);
+                value
+            }
+        }
+    }
+}
+
+/// Unit preferences, telemetry is always in SI units
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Units {
+    pub speed: SpeedUnit,
+    pub altitude: AltitudeUnit,
+    pub vertical_speed: VerticalSpeedUnit,
+    pub distance: DistanceUnit,
+    pub temperature: TemperatureUnit,
+}
+
+impl Units {
+    pub fn metric() -> Self {
+        Self {
+            speed: SpeedUnit::KilometerPerHour,
+            altitude: AltitudeUnit::Meter,
+            vertical_speed: VerticalSpeedUnit::MeterPerSecond,
+            distance: DistanceUnit::Kilometer,
+            temperature: TemperatureUnit::Celsius,
+        }
+    }
+}
+
+/// Multiply by numerator / denominator, rounded half away from zero
+fn scale(value: Decimal, numerator: i64, denominator: i64) -> Decimal {
+    let product = value.raw.saturating_mul(numerator);
+    let half = denominator / 2 * product.signum();
+    Decimal {
+        raw: (product + half) / denominator,
+        decimals: value.decimals,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::{fixed, FixedPoint};
+
+    use crate::format::{Decimal, Format};
+
+    use super::{AltitudeUnit, DistanceUnit, SpeedUnit, TemperatureUnit, VerticalSpeedUnit};
+
+    fn round(value: Decimal) -> i64 {
+        value.rescale(0).raw
+    }
+
+    #[test]
+    fn test_convert() {
+        let speed: FixedPoint<u16, 1> = fixed!(51.4);
+        assert_eq!(100, round(SpeedUnit::Knot.convert(speed)));
+        assert_eq!(185, round(SpeedUnit::KilometerPerHour.convert(speed)));
+        assert_eq!(115, round(SpeedUnit::MilePerHour.convert(speed)));
+        assert_eq!(51, round(SpeedUnit::MeterPerSecond.convert(speed)));
+
+        let altitude: FixedPoint<i32, 1> = fixed!(-304.8);
+        assert_eq!(-1000, round(AltitudeUnit::Feet.convert(altitude)));
+        assert_eq!(-305, round(AltitudeUnit::Meter.convert(altitude)));
+
+        let vario: FixedPoint<i16, 2> = fixed!(5.08);
+        assert_eq!(1000, round(VerticalSpeedUnit::FeetPerMinute.convert(vario)));
+
+        let nm = DistanceUnit::NauticalMile.convert(8704u32);
+        let format = Format {
+            decimals: 1,
+            ..Default::default()
+        };
+        assert_eq!(b"4.7", format.format(nm).as_slice());
+        assert_eq!(1000, round(DistanceUnit::Mile.convert(1_609_344u32)));
+
+        assert_eq!(212, round(TemperatureUnit::Fahrenheit.convert(100i16)));
+        assert_eq!(-40, round(TemperatureUnit::Fahrenheit.convert(-40i16)));
+    }
+}
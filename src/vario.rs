@@ -1,13 +1,21 @@
-use heapless::String;
-
+use crate::canvas::Canvas;
+use crate::clock::Clock;
 use crate::drawable::{Align, Drawable, NumOfLine};
-use crate::telemetry::Telemetry;
+use crate::format::Format;
+use crate::telemetry::{Quantity, Telemetry};
+use crate::unit::VerticalSpeedUnit;
 
-pub struct Vario(Align); // only accept TopRight or Right
+pub struct Vario(Align, VerticalSpeedUnit); // only accept TopRight or Right
 
 impl Default for Vario {
     fn default() -> Self {
-        Self(Align::Right)
+        Self(Align::Right, VerticalSpeedUnit::default())
+    }
+}
+
+impl Vario {
+    pub fn set_unit(&mut self, unit: VerticalSpeedUnit) {
+        self.1 = unit;
     }
 }
 
@@ -16,34 +24,50 @@ impl<T: AsMut<[u8]>> Drawable<T> for Vario {
         self.0
     }
 
-    fn draw(&self, telemetry: &Telemetry, output: &mut [T]) -> NumOfLine {
-        let buffer = output[0].as_mut();
-        let string: String<6> = telemetry.vario.into();
-        let bytes = string.as_bytes();
-        let offset = buffer.len() - bytes.len();
-        buffer[offset..].copy_from_slice(bytes);
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Vario]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let mut canvas = Canvas::new(output);
+        let width = canvas.width();
+        let format = Format {
+            decimals: self.1.decimals(),
+            ..Default::default()
+        };
+        let vario = self.1.convert(telemetry.vario);
+        let label = canvas.text(0, width, self.1.label(), Align::Right);
+        canvas.number(0, label.start, vario, &format, Align::Right);
         1
     }
 }
 
 #[cfg(test)]
 mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
     use crate::drawable::Drawable;
     use crate::telemetry::Telemetry;
-    use crate::test_utils::to_utf8_string;
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+    use crate::unit::VerticalSpeedUnit;
 
     use super::Vario;
 
     #[test]
-    fn test_altitude() {
-        let mut buffer = [[0u8; 6]];
-        let vario = Vario::default();
+    fn test_vario() {
+        let mut buffer = [[0u8; 9]];
+        let mut vario = Vario::default();
         let mut telemetry = Telemetry::default();
-        telemetry.vario = 1000;
-        vario.draw(&telemetry, &mut buffer);
-        assert_eq!("  1000", to_utf8_string(&buffer));
-        telemetry.vario = -1000;
-        vario.draw(&telemetry, &mut buffer);
-        assert_eq!(" -1000", to_utf8_string(&buffer));
+        telemetry.vario = fixed!(5.08);
+        vario.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  1000FPM", to_utf8_string(&buffer));
+        telemetry.vario = fixed!(-5.08);
+        vario.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!(" -1000FPM", to_utf8_string(&buffer));
+        buffer[0].zero();
+        vario.set_unit(VerticalSpeedUnit::MeterPerSecond);
+        vario.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("  -5.1M/S", to_utf8_string(&buffer));
     }
 }
@@ -0,0 +1,96 @@
+use crate::canvas::Canvas;
+use crate::clock::Clock;
+use crate::drawable::{Align, Drawable, NumOfLine};
+use crate::format::Format;
+use crate::symbol::{Symbol, SymbolIndex, SymbolTable};
+use crate::telemetry::{Quantity, Telemetry};
+use crate::unit::SpeedUnit;
+
+/// Arrow pointing where wind blows towards relative to heading, followed by wind speed
+pub struct Wind {
+    arrows: [SymbolIndex; 8], // clockwise from up
+    unit: SpeedUnit,
+}
+
+impl Wind {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        Self {
+            arrows: [
+                symbols[Symbol::ArrowUp],
+                symbols[Symbol::ArrowUpRight],
+                symbols[Symbol::ArrowRight],
+                symbols[Symbol::ArrowDownRight],
+                symbols[Symbol::ArrowDown],
+                symbols[Symbol::ArrowDownLeft],
+                symbols[Symbol::ArrowLeft],
+                symbols[Symbol::ArrowUpLeft],
+            ],
+            unit: SpeedUnit::default(),
+        }
+    }
+
+    pub fn set_unit(&mut self, unit: SpeedUnit) {
+        self.unit = unit;
+    }
+}
+
+impl<T: AsMut<[u8]>> Drawable<T> for Wind {
+    fn align(&self) -> Align {
+        Align::Left
+    }
+
+    fn quantities(&self) -> &[Quantity] {
+        &[Quantity::Wind, Quantity::Heading]
+    }
+
+    fn draw(&self, telemetry: &Telemetry, _: &Clock, output: &mut [T], _: &mut [T]) -> NumOfLine {
+        let wind = &telemetry.wind;
+        let towards = wind.direction as i32 + 180;
+        let relative = (towards - telemetry.heading as i32).rem_euclid(360);
+        let index = ((relative + 22) / 45) as usize % self.arrows.len();
+        let mut canvas = Canvas::new(output);
+        canvas.symbol(0, 0, self.arrows[index]);
+        let speed = self.unit.convert(wind.speed);
+        canvas.number(0, 1, speed, &Format::default(), Align::Left);
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fixed_point::fixed;
+
+    use crate::clock::Clock;
+    use crate::drawable::Drawable;
+    use crate::symbol::default_symbol_table;
+    use crate::telemetry::{Telemetry, Wind as WindStatus};
+    use crate::test_utils::{to_utf8_string, ZeroSlice};
+    use crate::unit::SpeedUnit;
+
+    use super::Wind;
+
+    #[test]
+    fn test_wind() {
+        let mut buffer = [[0u8; 4]];
+        let mut wind = Wind::new(&default_symbol_table());
+        let mut telemetry = Telemetry {
+            wind: WindStatus {
+                direction: 0,
+                speed: fixed!(5.0),
+            },
+            ..Default::default()
+        };
+        wind.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("↓10 ", to_utf8_string(&buffer));
+
+        buffer[0].zero();
+        telemetry.heading = 90;
+        wind.set_unit(SpeedUnit::MeterPerSecond);
+        wind.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("→5  ", to_utf8_string(&buffer));
+
+        telemetry.heading = 225;
+        wind.draw(&telemetry, &Clock::default(), &mut buffer, &mut []);
+        assert_eq!("↖5  ", to_utf8_string(&buffer));
+    }
+}